@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use advent_of_code::fuzz;
+
+fuzz_target!(|data: &[u8]| fuzz::parse(1, data));
@@ -0,0 +1,219 @@
+//! A small property-testing harness for comparing solvers against naive
+//! reference implementations.
+//!
+//! Cases are puzzle inputs in their text format. A failing case is shrunk
+//! to a minimal one, which is appended to the property's regression file
+//! and replayed first on every later run. Set `PROPERTY_SEED` to explore
+//! other random cases.
+
+use std::cell::Cell;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use crate::generate::Rng;
+
+const SEPARATOR: &str = "\n---\n";
+
+pub struct Property<'a> {
+    regressions: &'a str,
+    cases: usize,
+}
+
+impl<'a> Property<'a> {
+    /// A property whose minimised failing cases are kept in `regressions`.
+    pub fn new(regressions: &'a str) -> Self {
+        Property {
+            regressions,
+            cases: 200,
+        }
+    }
+
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Checks that `holds` is true for every saved regression case and for
+    /// random cases from `generate`. Panicking counts as not holding.
+    pub fn check<G, S, P>(&self, generate: G, shrink: S, holds: P)
+    where
+        G: Fn(&mut Rng) -> String,
+        S: Fn(&str) -> Vec<String>,
+        P: Fn(&str) -> bool,
+    {
+        for case in self.saved_cases() {
+            assert!(passes(&holds, &case), "Saved regression case fails:\n{}", case);
+        }
+
+        let seed = env::var("PROPERTY_SEED")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        let mut rng = Rng::new(seed);
+
+        for _ in 0..self.cases {
+            let case = generate(&mut rng);
+            if !passes(&holds, &case) {
+                let minimal = minimise(case, &shrink, &holds);
+                self.save_case(&minimal);
+                panic!(
+                    "Property fails for (saved to {}):\n{}",
+                    self.regressions, minimal
+                );
+            }
+        }
+    }
+
+    fn saved_cases(&self) -> Vec<String> {
+        fs::read_to_string(self.regressions)
+            .map(|text| {
+                text.split(SEPARATOR)
+                    .map(|case| case.trim_end_matches('\n').to_string())
+                    .filter(|case| !case.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn save_case(&self, case: &str) {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.regressions)
+            .expect("Failed to open regression file");
+        write!(file, "{}{}", case, SEPARATOR).expect("Failed to save regression case");
+    }
+}
+
+fn passes<P: Fn(&str) -> bool>(holds: &P, case: &str) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(|| holds(case))).unwrap_or(false)
+}
+
+/// Repeatedly replaces the case with the first smaller candidate that
+/// still fails, until no candidate does.
+fn minimise<S, P>(mut case: String, shrink: &S, holds: &P) -> String
+where
+    S: Fn(&str) -> Vec<String>,
+    P: Fn(&str) -> bool,
+{
+    quietly(|| {
+        while let Some(smaller) = shrink(&case).into_iter().find(|c| !passes(holds, c)) {
+            case = smaller;
+        }
+        case
+    })
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` without printing the messages of the panics it causes. The
+/// panic hook is only replaced once, by one that stays silent for threads
+/// inside `quietly`, so tests running in parallel still report theirs.
+fn quietly<T, F: FnOnce() -> T>(f: F) -> T {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                default(info)
+            }
+        }));
+    });
+
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            QUIET.with(|quiet| quiet.set(self.0));
+        }
+    }
+
+    let _restore = Restore(QUIET.with(|quiet| quiet.replace(true)));
+    f()
+}
+
+/// Candidates with chunks of decreasing size removed, from halves down to
+/// single items.
+fn without_chunks<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut candidates = vec![];
+    let mut size = items.len() / 2;
+
+    while size > 0 {
+        for start in (0..items.len()).step_by(size) {
+            let mut candidate = items[..start].to_vec();
+            candidate.extend_from_slice(&items[(start + size).min(items.len())..]);
+            candidates.push(candidate);
+        }
+        size /= 2;
+    }
+    if items.len() == 1 {
+        candidates.push(vec![]);
+    }
+
+    candidates
+}
+
+/// Shrinks by removing characters.
+pub fn shrink_chars(case: &str) -> Vec<String> {
+    let chars: Vec<char> = case.chars().collect();
+    without_chunks(&chars)
+        .into_iter()
+        .map(|c| c.into_iter().collect())
+        .collect()
+}
+
+/// Shrinks by removing lines.
+pub fn shrink_lines(case: &str) -> Vec<String> {
+    let lines: Vec<&str> = case.lines().collect();
+    without_chunks(&lines)
+        .into_iter()
+        .map(|c| c.join("\n"))
+        .collect()
+}
+
+/// Shrinks a whitespace-separated list by removing items.
+pub fn shrink_words(case: &str) -> Vec<String> {
+    let words: Vec<&str> = case.split_whitespace().collect();
+    without_chunks(&words)
+        .into_iter()
+        .map(|c| c.join(" "))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_minimises_a_failing_case() {
+        let holds = |case: &str| !(case.contains('x') && case.contains('y'));
+
+        assert_eq!(minimise("abxcdefyg".to_string(), &shrink_chars, &holds), "xy")
+    }
+
+    #[test]
+    fn it_treats_panics_as_failures() {
+        let result = quietly(|| passes(&|case: &str| case.parse::<u8>().is_ok() || panic!(), "x"));
+
+        assert!(!result)
+    }
+
+    #[test]
+    fn it_only_silences_panics_while_quiet() {
+        let result = panic::catch_unwind(|| quietly(|| panic!("expected")));
+
+        assert!(result.is_err());
+        assert!(!QUIET.with(Cell::get))
+    }
+
+    #[test]
+    fn it_shrinks_lines_and_words() {
+        assert!(shrink_lines("a\nb\nc").contains(&"a\nc".to_string()));
+        assert!(shrink_words("1 2 3 4").contains(&"3 4".to_string()));
+        assert_eq!(shrink_chars("a"), vec![""])
+    }
+}
@@ -0,0 +1,215 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::str::FromStr;
+use std::{fs::File, io::BufRead, io::BufReader};
+
+pub mod parse;
+
+use self::parse::*;
+
+pub fn map_lines_to_int32(path: &str) -> Vec<i32> {
+    load_integers(path).unwrap()
+}
+
+pub fn map_lines_to_strings(path: &str) -> Vec<String> {
+    let file = File::open(path).unwrap();
+    BufReader::new(file).lines().map(|l| l.unwrap()).collect()
+}
+
+pub fn read_file_to_string(path: &str) -> String {
+    let mut file = File::open(path).unwrap();
+    let mut result_string = String::new();
+    file.read_to_string(&mut result_string).expect("Failed to read file");
+    result_string
+}
+
+/// How numbers are laid out on each line of an input.
+#[derive(Debug, Clone)]
+pub struct NumberFormat {
+    separators: Vec<char>,
+    allow_plus: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            separators: vec![' ', '\t', ','],
+            allow_plus: true,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Characters that separate numbers on a line. Runs of separators
+    /// are treated as one.
+    pub fn separators(mut self, separators: &[char]) -> Self {
+        self.separators = separators.to_vec();
+        self
+    }
+
+    /// Whether a leading `+` is accepted, as in `+12`.
+    pub fn allow_plus(mut self, allow_plus: bool) -> Self {
+        self.allow_plus = allow_plus;
+        self
+    }
+}
+
+#[derive(Debug)]
+pub enum NumberError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NumberError::Io(error) => write!(f, "failed to read input: {}", error),
+            NumberError::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for NumberError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NumberError::Io(error) => Some(error),
+            NumberError::Parse(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for NumberError {
+    fn from(error: io::Error) -> Self {
+        NumberError::Io(error)
+    }
+}
+
+impl From<ParseError> for NumberError {
+    fn from(error: ParseError) -> Self {
+        NumberError::Parse(error)
+    }
+}
+
+/// An integer, rejecting a leading `+` unless the format allows it.
+fn number<'a, T: FromStr>(allow_plus: bool) -> impl Fn(Input<'a>) -> PResult<'a, T> {
+    move |input: Input<'a>| {
+        if !allow_plus && input.rest().starts_with('+') {
+            return Err(ParseError {
+                line: 1,
+                span: input.offset()..input.offset() + 1,
+                expected: "integer without a '+' sign".to_string(),
+                found: "+".to_string(),
+            });
+        }
+        integer()(input)
+    }
+}
+
+/// All the numbers on one line, possibly none, with separators allowed
+/// before and after them.
+fn numbers<'a, T: FromStr>(format: &NumberFormat) -> impl Fn(Input<'a>) -> PResult<'a, Vec<T>> {
+    let format = format.clone();
+    move |input: Input<'a>| {
+        let is_separator = |c: char| format.separators.contains(&c);
+        let (_, input) = take_while(is_separator)(input)?;
+        if input.is_empty() {
+            return Ok((vec![], input));
+        }
+
+        let separator = take_while1(is_separator, "separator");
+        let (values, input) = separated(number(format.allow_plus), separator)(input)?;
+        let (_, input) = take_while(is_separator)(input)?;
+        Ok((values, input))
+    }
+}
+
+/// Parses every line of `input` into the numbers it contains. Line
+/// numbers in errors start at 1.
+pub fn parse_numbers<T: FromStr>(input: &str, format: &NumberFormat) -> Result<Vec<Vec<T>>, NumberError> {
+    let parser = numbers(format);
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            parse_all(&parser, line).map_err(|e| NumberError::Parse(ParseError { line: index + 1, ..e }))
+        })
+        .collect()
+}
+
+pub fn load_numbers<T: FromStr>(path: &str, format: &NumberFormat) -> Result<Vec<Vec<T>>, NumberError> {
+    parse_numbers(&fs::read_to_string(path)?, format)
+}
+
+/// Loads one integer from each non-blank line of the file.
+pub fn load_integers<T: FromStr>(path: &str) -> Result<Vec<T>, NumberError> {
+    Ok(parse_lines(integer(), &fs::read_to_string(path)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_signed_numbers_one_per_line() {
+        let result: Vec<Vec<i64>> = parse_numbers("+7\n-3\n\n+10000000000", &NumberFormat::default()).unwrap();
+        assert_eq!(result, vec![vec![7], vec![-3], vec![], vec![10000000000]])
+    }
+
+    #[test]
+    fn it_parses_several_numbers_per_line() {
+        let format = NumberFormat::default().separators(&[',', ';']);
+        let result: Vec<Vec<u8>> = parse_numbers("1,2;3\n4,,5", &format).unwrap();
+        assert_eq!(result, vec![vec![1, 2, 3], vec![4, 5]])
+    }
+
+    #[test]
+    fn it_reports_the_line_of_an_invalid_number() {
+        let result = parse_numbers::<u8>("1 2\n3 300", &NumberFormat::default());
+
+        match result {
+            Err(NumberError::Parse(error)) => {
+                assert_eq!((error.line, error.span, error.found.as_str()), (2, 2..5, "300"))
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_rejects_a_plus_sign_when_disallowed() {
+        let format = NumberFormat::default().allow_plus(false);
+        let result = parse_numbers::<i32>("-1\n+1", &format);
+
+        match result {
+            Err(NumberError::Parse(error)) => assert_eq!((error.line, error.found.as_str()), (2, "+")),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_rejects_text_between_numbers() {
+        let result = parse_numbers::<i32>("1, 2\n3 x 4", &NumberFormat::default());
+
+        match result {
+            Err(NumberError::Parse(error)) => assert_eq!((error.line, error.found.as_str()), (2, "x")),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_loads_one_integer_per_line() {
+        let numbers: Vec<i64> = load_integers("./src/y2018/day1/input").unwrap();
+        assert_eq!(numbers.len(), map_lines_to_int32("./src/y2018/day1/input").len());
+
+        let result = parse_lines(integer::<i32>(), "1\n2, 3");
+        assert_eq!(result.map_err(|e| e.line), Err(2))
+    }
+
+    #[test]
+    fn it_reports_missing_files() {
+        let result = load_integers::<i32>("./src/day0/input");
+        assert!(matches!(result, Err(NumberError::Io(_))))
+    }
+}
@@ -0,0 +1,432 @@
+//! Small parser combinators for puzzle inputs.
+//!
+//! A parser is any `Fn(Input) -> PResult<T>`: on success it returns the
+//! parsed value and the remaining input, on failure a `ParseError` with
+//! the span where parsing stopped. Parsers are built from the primitives
+//! here and sequenced with `?`:
+//!
+//! ```
+//! use advent_of_code::common::parse::*;
+//!
+//! fn size(input: Input) -> PResult<(u32, u32)> {
+//!     let (width, input) = integer()(input)?;
+//!     let (_, input) = literal("x")(input)?;
+//!     let (height, input) = integer()(input)?;
+//!     Ok(((width, height), input))
+//! }
+//!
+//! assert_eq!(parse_all(size, "4x5"), Ok((4, 5)));
+//! ```
+
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// The part of a line or text that is left to parse, and where it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Input<'a> {
+    rest: &'a str,
+    offset: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Input {
+            rest: text,
+            offset: 0,
+        }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    /// Byte offset of the remaining input from the start of the text.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    fn advance(self, len: usize) -> (&'a str, Input<'a>) {
+        let (taken, rest) = self.rest.split_at(len);
+        (
+            taken,
+            Input {
+                rest,
+                offset: self.offset + len,
+            },
+        )
+    }
+
+    /// An error spanning the next token, i.e. everything up to the next
+    /// whitespace, or at least one character.
+    fn error(&self, expected: &str) -> ParseError {
+        let token = self
+            .rest
+            .char_indices()
+            .find(|&(i, c)| i > 0 && c.is_whitespace())
+            .map(|(i, _)| i)
+            .unwrap_or(self.rest.len());
+
+        ParseError {
+            line: 1,
+            span: self.offset..self.offset + token,
+            expected: expected.to_string(),
+            found: self.rest[..token].to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Byte range within the line.
+    pub span: Range<usize>,
+    pub expected: String,
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let found = if self.found.is_empty() {
+            "end of input".to_string()
+        } else {
+            format!("{:?}", self.found)
+        };
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line,
+            self.span.start + 1,
+            self.expected,
+            found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type PResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+/// Matches `expected` exactly.
+pub fn literal<'a>(expected: &'static str) -> impl Fn(Input<'a>) -> PResult<'a, &'a str> {
+    move |input: Input<'a>| {
+        if input.rest.starts_with(expected) {
+            Ok(input.advance(expected.len()))
+        } else {
+            Err(input.error(&format!("{:?}", expected)))
+        }
+    }
+}
+
+/// An optionally signed decimal integer of any `FromStr` type.
+pub fn integer<'a, T: FromStr>() -> impl Fn(Input<'a>) -> PResult<'a, T> {
+    move |input: Input<'a>| {
+        let sign = input.rest.starts_with(['+', '-']) as usize;
+        let digits = input.rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.rest.len() - sign);
+
+        if digits == 0 {
+            return Err(input.error("integer"));
+        }
+
+        let (text, rest) = input.advance(sign + digits);
+        match text.parse() {
+            Ok(value) => Ok((value, rest)),
+            Err(_) => Err(input.error("integer in range")),
+        }
+    }
+}
+
+/// One or more whitespace characters.
+pub fn space1<'a>() -> impl Fn(Input<'a>) -> PResult<'a, &'a str> {
+    move |input: Input<'a>| {
+        let len = input
+            .rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(input.rest.len());
+
+        if len == 0 {
+            Err(input.error("whitespace"))
+        } else {
+            Ok(input.advance(len))
+        }
+    }
+}
+
+/// Any amount of whitespace, including none.
+pub fn space0<'a>() -> impl Fn(Input<'a>) -> PResult<'a, &'a str> {
+    move |input: Input<'a>| {
+        let len = input
+            .rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(input.rest.len());
+        Ok(input.advance(len))
+    }
+}
+
+/// Any number of characters satisfying `valid`, including none.
+pub fn take_while<'a, F>(valid: F) -> impl Fn(Input<'a>) -> PResult<'a, &'a str>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: Input<'a>| {
+        let len = input.rest.find(|c: char| !valid(c)).unwrap_or(input.rest.len());
+        Ok(input.advance(len))
+    }
+}
+
+/// One or more characters satisfying `valid`.
+pub fn take_while1<'a, F>(valid: F, expected: &'static str) -> impl Fn(Input<'a>) -> PResult<'a, &'a str>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: Input<'a>| {
+        let len = input.rest.find(|c: char| !valid(c)).unwrap_or(input.rest.len());
+
+        if len == 0 {
+            Err(input.error(expected))
+        } else {
+            Ok(input.advance(len))
+        }
+    }
+}
+
+pub fn map<'a, A, B, P, F>(parser: P, f: F) -> impl Fn(Input<'a>) -> PResult<'a, B>
+where
+    P: Fn(Input<'a>) -> PResult<'a, A>,
+    F: Fn(A) -> B,
+{
+    move |input| parser(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// Fails with `expected` unless the parsed value satisfies `valid`. The
+/// error spans the text the value was parsed from.
+pub fn verify<'a, T, P, F>(parser: P, valid: F, expected: &'static str) -> impl Fn(Input<'a>) -> PResult<'a, T>
+where
+    P: Fn(Input<'a>) -> PResult<'a, T>,
+    F: Fn(&T) -> bool,
+{
+    move |input: Input<'a>| {
+        let (value, rest) = parser(input)?;
+        if valid(&value) {
+            Ok((value, rest))
+        } else {
+            let len = rest.offset - input.offset;
+            Err(ParseError {
+                line: 1,
+                span: input.offset..rest.offset,
+                expected: expected.to_string(),
+                found: input.rest[..len].to_string(),
+            })
+        }
+    }
+}
+
+/// Runs `second` only if `first` fails. When both fail the error is the
+/// one that got furthest, or a combination if they stopped at the same
+/// place.
+pub fn or<'a, T, P1, P2>(first: P1, second: P2) -> impl Fn(Input<'a>) -> PResult<'a, T>
+where
+    P1: Fn(Input<'a>) -> PResult<'a, T>,
+    P2: Fn(Input<'a>) -> PResult<'a, T>,
+{
+    move |input| {
+        first(input).or_else(|e1| {
+            second(input).map_err(|e2| {
+                if e1.span.start == e2.span.start {
+                    ParseError {
+                        expected: format!("{} or {}", e1.expected, e2.expected),
+                        ..e1
+                    }
+                } else if e1.span.start > e2.span.start {
+                    e1
+                } else {
+                    e2
+                }
+            })
+        })
+    }
+}
+
+/// `prefix` and then `value`, keeping only the value.
+pub fn preceded<'a, A, T, P1, P>(prefix: P1, value: P) -> impl Fn(Input<'a>) -> PResult<'a, T>
+where
+    P1: Fn(Input<'a>) -> PResult<'a, A>,
+    P: Fn(Input<'a>) -> PResult<'a, T>,
+{
+    move |input| {
+        let (_, input) = prefix(input)?;
+        value(input)
+    }
+}
+
+/// `prefix`, `value` and `suffix` in sequence, keeping only the value.
+pub fn delimited<'a, A, T, B, P1, P, P2>(
+    prefix: P1,
+    value: P,
+    suffix: P2,
+) -> impl Fn(Input<'a>) -> PResult<'a, T>
+where
+    P1: Fn(Input<'a>) -> PResult<'a, A>,
+    P: Fn(Input<'a>) -> PResult<'a, T>,
+    P2: Fn(Input<'a>) -> PResult<'a, B>,
+{
+    move |input| {
+        let (_, input) = prefix(input)?;
+        let (result, input) = value(input)?;
+        let (_, input) = suffix(input)?;
+        Ok((result, input))
+    }
+}
+
+/// One or more `item`s separated by `separator`. A separator that is not
+/// followed by an item is left unconsumed.
+pub fn separated<'a, T, S, P, PS>(item: P, separator: PS) -> impl Fn(Input<'a>) -> PResult<'a, Vec<T>>
+where
+    P: Fn(Input<'a>) -> PResult<'a, T>,
+    PS: Fn(Input<'a>) -> PResult<'a, S>,
+{
+    move |input| {
+        let (first, mut input) = item(input)?;
+        let mut items = vec![first];
+
+        while let Ok((_, after_separator)) = separator(input) {
+            match item(after_separator) {
+                Ok((next, rest)) => {
+                    items.push(next);
+                    input = rest;
+                }
+                Err(_) => break,
+            }
+        }
+
+        Ok((items, input))
+    }
+}
+
+/// Runs `parser` on the whole of `text`, allowing only trailing
+/// whitespace after it.
+pub fn parse_all<'a, T, P>(parser: P, text: &'a str) -> Result<T, ParseError>
+where
+    P: Fn(Input<'a>) -> PResult<'a, T>,
+{
+    let (value, rest) = parser(Input::new(text))?;
+    let (_, rest) = space0()(rest)?;
+
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(rest.error("end of input"))
+    }
+}
+
+/// Parses every non-blank line of `text` as one record. Errors carry the
+/// line number they occurred on.
+pub fn parse_lines<'a, T, P>(parser: P, text: &'a str) -> Result<Vec<T>, ParseError>
+where
+    P: Fn(Input<'a>) -> PResult<'a, T>,
+{
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            parse_all(&parser, line).map_err(|e| ParseError {
+                line: index + 1,
+                ..e
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(input: Input) -> PResult<(u32, u32)> {
+        let (width, input) = integer()(input)?;
+        let (_, input) = literal("x")(input)?;
+        let (height, input) = integer()(input)?;
+        Ok(((width, height), input))
+    }
+
+    #[test]
+    fn it_parses_signed_integers() {
+        assert_eq!(parse_all(integer(), "-12"), Ok(-12i32));
+        assert_eq!(parse_all(integer(), "+7"), Ok(7i64));
+        assert!(parse_all(integer::<u8>(), "256").is_err())
+    }
+
+    #[test]
+    fn it_takes_characters_while_they_match() {
+        let digits = |c: char| c.is_ascii_digit();
+        assert_eq!(parse_all(take_while(digits), ""), Ok(""));
+        assert_eq!(parse_all(take_while1(digits, "digits"), "123"), Ok("123"));
+        assert!(parse_all(take_while1(digits, "digits"), "x").is_err())
+    }
+
+    #[test]
+    fn it_keeps_the_value_after_a_prefix() {
+        assert_eq!(parse_all(preceded(space0(), integer()), "  12"), Ok(12u32));
+        assert!(parse_all(preceded(literal("#"), integer::<u32>()), "12").is_err())
+    }
+
+    #[test]
+    fn it_sequences_parsers() {
+        assert_eq!(parse_all(size, "4x5"), Ok((4, 5)))
+    }
+
+    #[test]
+    fn it_parses_separated_lists() {
+        let list = separated(integer::<i32>(), delimited(space0(), literal(","), space0()));
+
+        assert_eq!(parse_all(&list, "1, 2 ,3"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_all(separated(integer::<i32>(), space1()), "1 2 3\n"), Ok(vec![1, 2, 3]))
+    }
+
+    #[test]
+    fn it_tries_alternatives() {
+        let sign = or(map(literal("on"), |_| true), map(literal("off"), |_| false));
+
+        assert_eq!(parse_all(&sign, "off"), Ok(false));
+        assert_eq!(parse_all(&sign, "maybe").unwrap_err().expected, "\"on\" or \"off\"")
+    }
+
+    #[test]
+    fn it_reports_the_span_of_an_error() {
+        let error = parse_all(size, "12xab 3").unwrap_err();
+
+        assert_eq!(error.span, 3..5);
+        assert_eq!(error.found, "ab");
+        assert_eq!(error.to_string(), "line 1, column 4: expected integer, found \"ab\"")
+    }
+
+    #[test]
+    fn it_rejects_trailing_input() {
+        let error = parse_all(size, "1x2 x").unwrap_err();
+        assert_eq!((error.span, error.expected.as_str()), (4..5, "end of input"))
+    }
+
+    #[test]
+    fn it_parses_line_records_with_line_numbers() {
+        assert_eq!(parse_lines(size, "1x2\n\n3x4\n"), Ok(vec![(1, 2), (3, 4)]));
+
+        let error = parse_lines(size, "1x2\n3y4").unwrap_err();
+        assert_eq!((error.line, error.span), (2, 1..3))
+    }
+
+    #[test]
+    fn it_verifies_parsed_values() {
+        let minute = verify(integer::<u32>(), |&m| m < 60, "minute");
+
+        assert_eq!(parse_all(&minute, "59"), Ok(59));
+
+        let error = parse_all(&minute, "60").unwrap_err();
+        assert_eq!((error.span, error.expected.as_str(), error.found.as_str()), (0..2, "minute", "60"))
+    }
+}
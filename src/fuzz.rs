@@ -0,0 +1,148 @@
+//! Entry points for the fuzz targets in `fuzz/`, which cover the 2018
+//! days. Each takes arbitrary bytes and must return, without panicking,
+//! whatever they are.
+
+use std::str;
+
+use crate::common::parse::*;
+use crate::common::{parse_numbers, NumberFormat};
+use crate::runner;
+use crate::y2018::{day3, day4, day8};
+
+/// The days that have a parser of their own. Days 2 and 5 take their
+/// input as plain lines or text.
+pub const PARSERS: &[u32] = &[1, 3, 4, 8];
+
+/// The days with a solver target.
+pub const SOLVERS: &[u32] = &[1, 2, 3, 4, 5, 8];
+
+/// Runs the parsers of `day` on `data`.
+pub fn parse(day: u32, data: &[u8]) {
+    if day == 4 {
+        let _ = day4::ingest_logs(vec![data]);
+    }
+
+    let input = match str::from_utf8(data) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+
+    match day {
+        1 => {
+            let _ = parse_numbers::<i64>(input, &NumberFormat::default());
+            let _ = parse_lines(integer::<i64>(), input);
+        }
+        3 => {
+            let _ = day3::parse_input(input);
+        }
+        4 => {
+            let _ = day4::parse_input(input);
+        }
+        8 => {
+            let _ = day8::try_parse_line(input);
+        }
+        _ => {}
+    }
+}
+
+/// Solves both parts of `day` with `data` as the input.
+pub fn solve(day: u32, data: &[u8]) {
+    if let Ok(input) = str::from_utf8(data) {
+        for part in 1..=2 {
+            let _ = runner::solve_input(2018, day, part, input);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+    use std::fs;
+
+    type Target = fn(u32, &[u8]);
+
+    /// Every target, as named in `fuzz/Cargo.toml`.
+    fn targets() -> Vec<(String, Target, u32)> {
+        let parsers = PARSERS
+            .iter()
+            .map(|&day| (format!("parse_day{}", day), parse as Target, day));
+        let solvers = SOLVERS
+            .iter()
+            .map(|&day| (format!("solve_day{}", day), solve as Target, day));
+        parsers.chain(solvers).collect()
+    }
+
+    fn corpus(target: &str) -> Vec<Vec<u8>> {
+        let dir = format!("./fuzz/corpus/{}", target);
+        fs::read_dir(&dir)
+            .unwrap_or_else(|_| panic!("Missing corpus {}", dir))
+            .map(|entry| fs::read(entry.unwrap().path()).unwrap())
+            .collect()
+    }
+
+    /// A small random edit of the kind a fuzzer would make.
+    fn mutate(rng: &mut Rng, data: &mut Vec<u8>) {
+        const TOKENS: &[&str] = &["0", "-1", "2147483647", "-2147483648", "4294967295", " ", "\n", "#"];
+
+        let at = rng.below(data.len() as u64 + 1) as usize;
+        match rng.below(4) {
+            0 if at < data.len() => data[at] ^= 1 << rng.below(8),
+            1 if at < data.len() => {
+                let end = (at + 1 + rng.below(8) as usize).min(data.len());
+                data.drain(at..end);
+            }
+            2 => {
+                let token = TOKENS[rng.below(TOKENS.len() as u64) as usize];
+                data.splice(at..at, token.bytes());
+            }
+            _ => data.insert(at, rng.below(256) as u8),
+        }
+    }
+
+    #[test]
+    fn it_has_a_seeded_corpus_for_every_target() {
+        for (target, _, _) in targets() {
+            assert!(!corpus(&target).is_empty(), "Empty corpus for {}", target);
+        }
+    }
+
+    #[test]
+    fn it_survives_the_corpus() {
+        for (target, run, day) in targets() {
+            for case in corpus(&target) {
+                run(day, &case);
+            }
+        }
+    }
+
+    #[test]
+    fn it_survives_mutated_inputs() {
+        let mut rng = Rng::new(0);
+
+        for (target, run, day) in targets() {
+            let mut seeds = corpus(&target);
+            seeds.push(generate::input(day, &mut rng, Some(20)).unwrap().into_bytes());
+
+            for i in 0..300 {
+                let mut case = seeds[i % seeds.len()].clone();
+                for _ in 0..1 + rng.below(4) {
+                    mutate(&mut rng, &mut case);
+                }
+                run(day, &case);
+            }
+        }
+    }
+
+    #[test]
+    fn it_survives_arbitrary_bytes() {
+        let mut rng = Rng::new(1);
+
+        for (_, run, day) in targets() {
+            for _ in 0..200 {
+                let case: Vec<u8> = (0..rng.below(64)).map(|_| rng.below(256) as u8).collect();
+                run(day, &case);
+            }
+        }
+    }
+}
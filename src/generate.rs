@@ -0,0 +1,459 @@
+//! Random puzzle inputs for stress testing and fuzzing the solvers.
+//!
+//! Every generator takes the random source and a size, and returns text in
+//! the same format as the day's `input` file. The same seed always gives
+//! the same input.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+use crate::y2018::day8::Tree;
+use crate::grid::{Point, Rect};
+
+/// A small SplitMix64 generator, so that inputs are reproducible from a
+/// seed without any dependencies.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`. `bound` must not be 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// A number in `low..=high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as u64) as i64
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn letter(&mut self, alphabet: usize) -> char {
+        (b'a' + self.below(alphabet as u64) as u8) as char
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Day 1: `size` frequency changes, one signed number per line.
+pub fn frequencies(rng: &mut Rng, size: usize) -> String {
+    let lines: Vec<String> = (0..size)
+        .map(|_| {
+            let change = match rng.range(-100, 99) {
+                n if n >= 0 => n + 1,
+                n => n,
+            };
+            format!("{:+}", change)
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Day 2: `size` box IDs of `length` letters, of which exactly one pair
+/// differs in a single position. No two IDs are equal. Each ID rules out
+/// itself and the `25 * length` IDs one letter away, so `size` is capped
+/// to keep at least half of all IDs free, which keeps retries rare.
+pub fn box_ids(rng: &mut Rng, size: usize, length: usize) -> String {
+    let length = length.max(2);
+    let capacity = 26usize
+        .checked_pow(length as u32)
+        .map_or(usize::MAX, |ids| ids / (2 * (1 + 25 * length)));
+    let size = size.clamp(2, capacity.max(2));
+    let random_id = |rng: &mut Rng| -> Vec<char> { (0..length).map(|_| rng.letter(26)).collect() };
+
+    // Two IDs are at most one position apart exactly when deleting the
+    // same position from both leaves the same letters.
+    let deletions = |id: &[char]| {
+        (0..length)
+            .map(|position| {
+                let mut rest = id.to_vec();
+                rest.remove(position);
+                (position, rest)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let original = random_id(rng);
+    let mut twin = original.clone();
+    let position = rng.below(length as u64) as usize;
+    twin[position] = (b'a' + (twin[position] as u8 - b'a' + 1 + rng.below(25) as u8) % 26) as char;
+
+    let mut taken: HashSet<(usize, Vec<char>)> = HashSet::new();
+    taken.extend(deletions(&original));
+    taken.extend(deletions(&twin));
+    let mut ids = vec![original, twin];
+
+    while ids.len() < size {
+        let id = random_id(rng);
+        let keys = deletions(&id);
+        if keys.iter().all(|key| !taken.contains(key)) {
+            taken.extend(keys);
+            ids.push(id);
+        }
+    }
+
+    rng.shuffle(&mut ids);
+    ids.iter()
+        .map(|id| id.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 3: `size` claims on a square of fabric. Every claim overlaps
+/// another one, except for a single intact claim.
+pub fn claims(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+    let fabric = 40 + 8 * size as i64;
+    let random_rect = |rng: &mut Rng| {
+        let (w, h) = (rng.range(1, 30), rng.range(1, 30));
+        Rect::new(
+            Point::new(rng.range(0, fabric - w), rng.range(0, fabric - h)),
+            w,
+            h,
+        )
+    };
+
+    let intact = random_rect(rng);
+    let mut rects = vec![intact];
+
+    while rects.len() < size {
+        // Claims are added in overlapping pairs. A last odd claim overlaps
+        // one of the existing claims instead.
+        let as_pair = size - rects.len() >= 2;
+        let anchor = if as_pair {
+            random_rect(rng)
+        } else {
+            rects[1 + rng.below(rects.len() as u64 - 1) as usize]
+        };
+        let overlapping = Rect::new(
+            Point::new(
+                (anchor.min.x + rng.range(1 - anchor.width(), anchor.width() - 1)).max(0),
+                (anchor.min.y + rng.range(1 - anchor.height(), anchor.height() - 1)).max(0),
+            ),
+            rng.range(1, 30),
+            rng.range(1, 30),
+        );
+
+        if anchor.intersection(&intact).is_some()
+            || overlapping.intersection(&intact).is_some()
+            || overlapping.intersection(&anchor).is_none()
+        {
+            continue;
+        }
+        if as_pair {
+            rects.push(anchor);
+        }
+        rects.push(overlapping);
+    }
+
+    rng.shuffle(&mut rects);
+    rects
+        .iter()
+        .enumerate()
+        .map(|(i, r)| format!("#{} @ {},{}: {}x{}", i + 1, r.min.x, r.min.y, r.width(), r.height()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 4: a shuffled guard log covering `size` consecutive shifts, each
+/// with a guard from a pool and zero or more naps within the midnight
+/// hour. There are at most 360 shifts so that the log stays within one
+/// year.
+///
+/// The guard of the first shift sleeps through one planted minute on each
+/// of their shifts and works two shifts in three, so they are the unique
+/// answer to strategy 2. Their first shift has a single nap of exactly
+/// that minute.
+pub fn guard_log(rng: &mut Rng, size: usize) -> String {
+    const DAYS_IN_MONTH: [u32; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+    let size = size.clamp(1, 360);
+    let mut guards: Vec<u32> = vec![];
+    while guards.len() < (size / 4).max(1) {
+        let guard = rng.range(1, 3500) as u32;
+        if !guards.contains(&guard) {
+            guards.push(guard);
+        }
+    }
+    let (sleeper, others) = guards.split_first().unwrap();
+    let planted = rng.range(4, 58);
+    let mut lines = vec![];
+
+    // Shifts start at 23:xx the evening before, so the first shift is on
+    // January 2nd at the earliest and the last within the same year.
+    let (mut month, mut day) = (1, 1);
+    let next_day = |month: &mut u32, day: &mut u32| {
+        *day += 1;
+        if *day > DAYS_IN_MONTH[*month as usize - 1] {
+            *day = 1;
+            *month += 1;
+        }
+    };
+    for _ in 0..rng.below(361 - size as u64) {
+        next_day(&mut month, &mut day);
+    }
+
+    for shift in 0..size {
+        let (previous_month, previous_day) = (month, day);
+        next_day(&mut month, &mut day);
+
+        let guard = if others.is_empty() || shift % 3 != 1 {
+            *sleeper
+        } else {
+            others[rng.below(others.len() as u64) as usize]
+        };
+        let begin = if rng.chance(1, 2) {
+            format!("[1518-{:02}-{:02} 23:{:02}]", previous_month, previous_day, rng.range(45, 59))
+        } else {
+            format!("[1518-{:02}-{:02} 00:{:02}]", month, day, rng.range(0, 3))
+        };
+        lines.push(format!("{} Guard #{} begins shift", begin, guard));
+
+        let mut minutes: Vec<i64> = vec![];
+        if shift == 0 {
+            minutes = vec![planted, planted + 1];
+        } else if guard == *sleeper {
+            minutes = vec![rng.range(4, planted), rng.range(planted + 1, 59)];
+        } else {
+            let naps = rng.below(4);
+            while (minutes.len() as u64) < naps * 2 {
+                let minute = rng.range(4, 59);
+                if !minutes.contains(&minute) {
+                    minutes.push(minute);
+                }
+            }
+            minutes.sort_unstable();
+        }
+
+        for nap in minutes.chunks(2) {
+            lines.push(format!("[1518-{:02}-{:02} 00:{:02}] falls asleep", month, day, nap[0]));
+            lines.push(format!("[1518-{:02}-{:02} 00:{:02}] wakes up", month, day, nap[1]));
+        }
+    }
+
+    rng.shuffle(&mut lines);
+    lines.join("\n")
+}
+
+/// Day 5: a polymer of `size` units drawn from the first `types` letters,
+/// with reacting pairs planted throughout.
+pub fn polymer(rng: &mut Rng, size: usize, types: usize) -> String {
+    let types = types.clamp(1, 26);
+    let mut units: Vec<char> = Vec::with_capacity(size);
+
+    while units.len() < size {
+        let unit = rng.letter(types);
+        let unit = if rng.chance(1, 2) { unit.to_ascii_uppercase() } else { unit };
+        let at = rng.below(units.len() as u64 + 1) as usize;
+
+        if units.len() + 2 <= size && rng.chance(1, 3) {
+            let opposite = if unit.is_ascii_uppercase() {
+                unit.to_ascii_lowercase()
+            } else {
+                unit.to_ascii_uppercase()
+            };
+            units.splice(at..at, vec![unit, opposite]);
+        } else {
+            units.insert(at, unit);
+        }
+    }
+
+    units.into_iter().collect()
+}
+
+/// Day 8: a license tree with `size` nodes. Every node has between one
+/// and three metadata entries. Entries of nodes with children refer to a
+/// child or to the one just past the last.
+pub fn license_tree(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    // Each node after the root picks an earlier node as its parent, so
+    // building from the last node backwards always finds children done.
+    let parents: Vec<usize> = (1..size).map(|i| rng.below(i as u64) as usize).collect();
+    let mut children: BTreeMap<usize, Vec<Tree<i32>>> = BTreeMap::new();
+
+    for node in (0..size).rev() {
+        let mut own_children = children.remove(&node).unwrap_or_default();
+        own_children.reverse();
+
+        let mut builder = Tree::builder();
+        let highest = match own_children.len() {
+            0 => 9,
+            n => n as i64 + 1,
+        };
+        for child in own_children {
+            builder = builder.child(child);
+        }
+        for _ in 0..rng.range(1, 3) {
+            builder = builder.metadata(rng.range(1, highest) as i32);
+        }
+
+        let tree = builder.build();
+        if node == 0 {
+            return tree.to_string();
+        }
+        children.entry(parents[node - 1]).or_default().push(tree);
+    }
+
+    unreachable!()
+}
+
+/// A random input for `day`, of roughly the size of the real input unless
+/// `size` is given. Returns `None` for days without a generator.
+pub fn input(day: u32, rng: &mut Rng, size: Option<usize>) -> Option<String> {
+    let input = match day {
+        1 => frequencies(rng, size.unwrap_or(1000)),
+        2 => box_ids(rng, size.unwrap_or(250), 26),
+        3 => claims(rng, size.unwrap_or(1300)),
+        4 => guard_log(rng, size.unwrap_or(300)),
+        5 => polymer(rng, size.unwrap_or(50000), 26),
+        8 => license_tree(rng, size.unwrap_or(1500)),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::parse::{integer, parse_lines};
+    use crate::y2018::day2::IdIndex;
+    use crate::y2018::day4::GuardEvent;
+    use crate::y2018::{day1, day2, day3, day4, day5, day8};
+
+    #[test]
+    fn it_is_reproducible_from_a_seed() {
+        assert_eq!(
+            frequencies(&mut Rng::new(7), 50),
+            frequencies(&mut Rng::new(7), 50)
+        );
+        assert_ne!(
+            frequencies(&mut Rng::new(7), 50),
+            frequencies(&mut Rng::new(8), 50)
+        )
+    }
+
+    #[test]
+    fn it_generates_frequency_changes() {
+        let input = frequencies(&mut Rng::new(1), 300);
+        let changes: Vec<i64> = parse_lines(integer(), &input).unwrap();
+
+        assert_eq!(changes.len(), 300);
+        assert!(changes.iter().all(|&c| c != 0));
+        assert_eq!(day1::solve1(&changes), changes.iter().sum::<i64>())
+    }
+
+    #[test]
+    fn it_generates_box_ids_with_one_close_pair() {
+        for seed in 0..20 {
+            let input = box_ids(&mut Rng::new(seed), 100, 8);
+            let ids: Vec<&str> = input.lines().collect();
+
+            assert_eq!(ids.len(), 100);
+            assert_eq!(IdIndex::new(&ids).pairs_at_distance(1).len(), 1);
+            assert_eq!(IdIndex::new(&ids).pairs_at_distance(0).len(), 0);
+            assert_eq!(day2::solve2(ids).len(), 7);
+        }
+    }
+
+    #[test]
+    fn it_generates_box_ids_when_few_ids_fit() {
+        let input = box_ids(&mut Rng::new(5), 1000, 2);
+        let ids: Vec<&str> = input.lines().collect();
+
+        assert!(ids.len() >= 2 && ids.len() < 1000);
+        assert_eq!(IdIndex::new(&ids).pairs_at_distance(1).len(), 1);
+        assert_eq!(IdIndex::new(&ids).pairs_at_distance(0).len(), 0);
+    }
+
+    #[test]
+    fn it_generates_claims_with_one_intact_claim() {
+        for seed in 0..20 {
+            let input = claims(&mut Rng::new(seed), 60);
+            let claims: Vec<_> = input.lines().map(day3::parse_line).collect();
+
+            let intact: Vec<u32> = claims
+                .iter()
+                .filter(|&&(id, (x, y), (w, h))| {
+                    let rect = Rect::new(Point::new(x, y), w, h);
+                    claims.iter().all(|&(other, (ox, oy), (ow, oh))| {
+                        other == id || rect.intersection(&Rect::new(Point::new(ox, oy), ow, oh)).is_none()
+                    })
+                })
+                .map(|&(id, _, _)| id)
+                .collect();
+
+            assert_eq!(claims.len(), 60);
+            assert_eq!(intact.len(), 1, "seed {}", seed);
+            assert_eq!(day3::solve2(claims), intact[0]);
+        }
+    }
+
+    #[test]
+    fn it_generates_consistent_guard_logs() {
+        for seed in 0..20 {
+            let input = guard_log(&mut Rng::new(seed), 40);
+            let mut lines: Vec<&str> = input.lines().collect();
+            lines.sort_unstable();
+            let sorted = lines.join("\n");
+
+            let calendar = day4::ingest_logs(vec![input.as_bytes()]).unwrap();
+
+            assert!(!calendar.is_empty());
+            assert_eq!(calendar, day4::ingest_logs(vec![sorted.as_bytes()]).unwrap());
+            assert!(calendar.values().flat_map(|m| m.keys()).all(|&m| m < 60));
+
+            // The first shift is the planted sleeper's single nap.
+            let events: Vec<_> = lines.into_iter().map(day4::parse_line).collect();
+            let (guard, minute) = match &events[..3] {
+                [
+                    (_, GuardEvent::BeginShift(guard)),
+                    ((_, _, 0, minute), GuardEvent::FallAsleep),
+                    (_, GuardEvent::WakeUp),
+                ] => (*guard, *minute),
+                other => panic!("seed {}: unexpected first shift {:?}", seed, other),
+            };
+            assert_eq!(day4::solve2(events), guard as u64 * minute as u64, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn it_generates_polymers() {
+        let input = polymer(&mut Rng::new(3), 500, 4);
+
+        assert_eq!(input.len(), 500);
+        assert!(input.chars().all(|c| "abcdABCD".contains(c)));
+        assert!(day5::solve1(&input).len() < 500)
+    }
+
+    #[test]
+    fn it_generates_license_trees() {
+        for seed in 0..20 {
+            let input = license_tree(&mut Rng::new(seed), 200);
+            let tree = day8::parse_line(&input);
+
+            assert_eq!(tree.node_count(), 200);
+            assert!(tree.pre_order().all(|node| !node.metadata().is_empty()));
+            assert_eq!(tree.to_string(), input);
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A directed graph stored as adjacency lists, with nodes identified by
+/// any hashable value. Nodes keep the order in which they were added.
+#[derive(Debug, Clone)]
+pub struct Digraph<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    successors: Vec<Vec<usize>>,
+}
+
+/// The nodes of a cycle, in order. Each node has an edge to the next one
+/// and the last node has an edge back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Clone + Eq + Hash> Default for Digraph<N> {
+    fn default() -> Self {
+        Digraph {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            successors: Vec::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Digraph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node unless it is already in the graph.
+    pub fn add_node(&mut self, node: N) {
+        self.id(node);
+    }
+
+    /// Adds an edge, and the nodes at either end if they are missing.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let (from, to) = (self.id(from), self.id(to));
+        if !self.successors[from].contains(&to) {
+            self.successors[from].push(to);
+        }
+    }
+
+    fn id(&mut self, node: N) -> usize {
+        if let Some(&id) = self.index.get(&node) {
+            return id;
+        }
+        let id = self.nodes.len();
+        self.index.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.successors.push(Vec::new());
+        id
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.index.contains_key(node)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (&N, &N)> {
+        self.successors.iter().enumerate().flat_map(move |(from, tos)| {
+            tos.iter().map(move |&to| (&self.nodes[from], &self.nodes[to]))
+        })
+    }
+
+    /// The nodes `node` has an edge to, in the order the edges were added.
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> {
+        let ids = self.index.get(node).map(|&id| self.successors[id].as_slice());
+        ids.unwrap_or(&[]).iter().map(move |&id| &self.nodes[id])
+    }
+
+    /// The nodes that have an edge to `node`.
+    pub fn predecessors<'a>(&'a self, node: &'a N) -> impl Iterator<Item = &'a N> + 'a {
+        let target = self.index.get(node).cloned();
+        self.successors
+            .iter()
+            .enumerate()
+            .filter(move |(_, tos)| target.is_some_and(|t| tos.contains(&t)))
+            .map(move |(from, _)| &self.nodes[from])
+    }
+
+    fn in_degrees(&self) -> Vec<usize> {
+        let mut in_degrees = vec![0; self.nodes.len()];
+        for &to in self.successors.iter().flatten() {
+            in_degrees[to] += 1;
+        }
+        in_degrees
+    }
+
+    /// Kahn's algorithm, always taking the available node with the lowest
+    /// key next. Use `Reverse` in the key to take the highest first.
+    pub fn topological_sort_by_key<K, F>(&self, key: F) -> Result<Vec<N>, Cycle<N>>
+    where
+        K: Ord,
+        F: Fn(&N) -> K,
+    {
+        let mut in_degrees = self.in_degrees();
+        let mut available: BinaryHeap<Reverse<(K, usize)>> = in_degrees
+            .iter()
+            .enumerate()
+            .filter(|&(_, &d)| d == 0)
+            .map(|(id, _)| Reverse((key(&self.nodes[id]), id)))
+            .collect();
+
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(Reverse((_, id))) = available.pop() {
+            order.push(self.nodes[id].clone());
+            for &to in self.successors[id].iter() {
+                in_degrees[to] -= 1;
+                if in_degrees[to] == 0 {
+                    available.push(Reverse((key(&self.nodes[to]), to)));
+                }
+            }
+        }
+
+        if order.len() == self.nodes.len() {
+            Ok(order)
+        } else {
+            Err(self.find_cycle().expect("Unsorted nodes must contain a cycle"))
+        }
+    }
+
+    /// Finds some cycle in the graph, if there is one.
+    pub fn find_cycle(&self) -> Option<Cycle<N>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.nodes.len()];
+
+        for start in 0..self.nodes.len() {
+            if state[start] != State::New {
+                continue;
+            }
+
+            // The path from `start`, with the next successor to try for
+            // each node on it.
+            let mut path: Vec<(usize, usize)> = vec![(start, 0)];
+            state[start] = State::OnPath;
+
+            while let Some(&mut (node, ref mut next)) = path.last_mut() {
+                match self.successors[node].get(*next) {
+                    Some(&to) => {
+                        *next += 1;
+                        match state[to] {
+                            State::New => {
+                                state[to] = State::OnPath;
+                                path.push((to, 0));
+                            }
+                            State::OnPath => {
+                                let from = path.iter().position(|&(n, _)| n == to).unwrap();
+                                let cycle = path[from..]
+                                    .iter()
+                                    .map(|&(n, _)| self.nodes[n].clone())
+                                    .collect();
+                                return Some(Cycle(cycle));
+                            }
+                            State::Done => {}
+                        }
+                    }
+                    None => {
+                        state[node] = State::Done;
+                        path.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Nodes reachable from `start` in breadth-first order, starting with
+    /// `start` itself.
+    pub fn bfs(&self, start: &N) -> Bfs<'_, N> {
+        let mut visited = vec![false; self.nodes.len()];
+        let queue = self
+            .index
+            .get(start)
+            .map(|&id| {
+                visited[id] = true;
+                vec![id].into()
+            })
+            .unwrap_or_default();
+
+        Bfs {
+            graph: self,
+            queue,
+            visited,
+        }
+    }
+
+    /// Nodes reachable from `start` in depth-first pre-order, starting
+    /// with `start` itself.
+    pub fn dfs(&self, start: &N) -> Dfs<'_, N> {
+        Dfs {
+            graph: self,
+            stack: self.index.get(start).cloned().into_iter().collect(),
+            visited: vec![false; self.nodes.len()],
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash + Ord> Digraph<N> {
+    /// Topological order, taking the lexicographically smallest available
+    /// node first.
+    pub fn topological_sort(&self) -> Result<Vec<N>, Cycle<N>> {
+        self.topological_sort_by_key(|node| node.clone())
+    }
+}
+
+pub struct Bfs<'a, N> {
+    graph: &'a Digraph<N>,
+    queue: VecDeque<usize>,
+    visited: Vec<bool>,
+}
+
+impl<'a, N> Iterator for Bfs<'a, N> {
+    type Item = &'a N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.queue.pop_front()?;
+        for &to in self.graph.successors[id].iter() {
+            if !self.visited[to] {
+                self.visited[to] = true;
+                self.queue.push_back(to);
+            }
+        }
+        Some(&self.graph.nodes[id])
+    }
+}
+
+pub struct Dfs<'a, N> {
+    graph: &'a Digraph<N>,
+    stack: Vec<usize>,
+    visited: Vec<bool>,
+}
+
+impl<'a, N> Iterator for Dfs<'a, N> {
+    type Item = &'a N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let id = self.stack.pop()?;
+            if self.visited[id] {
+                continue;
+            }
+            self.visited[id] = true;
+            self.stack.extend(
+                self.graph.successors[id]
+                    .iter()
+                    .rev()
+                    .filter(|&&to| !self.visited[to]),
+            );
+            return Some(&self.graph.nodes[id]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The step dependencies from the day 7 example.
+    fn example() -> Digraph<char> {
+        let mut graph = Digraph::new();
+        for (from, to) in [
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ] {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    #[test]
+    fn it_sorts_topologically_in_lexicographic_order() {
+        let order: String = example().topological_sort().unwrap().into_iter().collect();
+        assert_eq!(order, "CABDFE")
+    }
+
+    #[test]
+    fn it_sorts_topologically_by_priority() {
+        let order: String = example()
+            .topological_sort_by_key(|&c| Reverse(c))
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(order, "CFADBE")
+    }
+
+    #[test]
+    fn it_reports_the_offending_cycle() {
+        let mut graph = example();
+        graph.add_edge('E', 'X');
+        graph.add_edge('X', 'A');
+
+        let cycle = graph.topological_sort().unwrap_err();
+
+        assert_eq!(cycle, Cycle(vec!['A', 'B', 'E', 'X']));
+        assert_eq!(example().find_cycle(), None)
+    }
+
+    #[test]
+    fn it_finds_self_loops() {
+        let mut graph = Digraph::new();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 2);
+
+        assert_eq!(graph.find_cycle(), Some(Cycle(vec![2])))
+    }
+
+    #[test]
+    fn it_walks_the_graph_breadth_and_depth_first() {
+        let graph = example();
+
+        let bfs: String = graph.bfs(&'C').collect();
+        let dfs: String = graph.dfs(&'C').collect();
+
+        assert_eq!(bfs, "CAFBDE");
+        assert_eq!(dfs, "CABEDF");
+        assert_eq!(graph.dfs(&'D').collect::<String>(), "DE");
+        assert_eq!(graph.bfs(&'Z').count(), 0)
+    }
+
+    #[test]
+    fn it_lists_neighbours() {
+        let graph = example();
+
+        assert_eq!(graph.successors(&'A').collect::<String>(), "BD");
+        assert_eq!(graph.predecessors(&'E').collect::<String>(), "FBD");
+        assert_eq!(graph.edges().count(), 7);
+        assert_eq!(graph.len(), 6)
+    }
+}
@@ -0,0 +1,526 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+/// Integer types usable as point coordinates.
+pub trait Coordinate:
+    Copy + Ord + Hash + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    /// The distance between two values, which never underflows.
+    fn distance(self, other: Self) -> Self;
+    fn to_usize(self) -> Option<usize>;
+    fn from_usize(value: usize) -> Option<Self>;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn distance(self, other: Self) -> Self {
+                    self.max(other) - self.min(other)
+                }
+
+                fn to_usize(self) -> Option<usize> {
+                    usize::try_from(self).ok()
+                }
+
+                fn from_usize(value: usize) -> Option<Self> {
+                    <$t>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i32, i64, isize, u32, u64, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coordinate> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+
+    pub fn origin() -> Self {
+        Point::new(T::ZERO, T::ZERO)
+    }
+
+    pub fn manhattan_distance(self, other: Point<T>) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    pub fn checked_add(self, other: Point<T>) -> Option<Self> {
+        Some(Point::new(self.x.checked_add(other.x)?, self.y.checked_add(other.y)?))
+    }
+
+    /// The up to four orthogonally adjacent points. Points that would fall
+    /// outside the coordinate type, such as below 0 for unsigned points,
+    /// are left out.
+    pub fn neighbours(self) -> Vec<Point<T>> {
+        let (x, y) = (self.x, self.y);
+        vec![
+            y.checked_sub(T::ONE).map(|y| Point::new(x, y)),
+            x.checked_sub(T::ONE).map(|x| Point::new(x, y)),
+            x.checked_add(T::ONE).map(|x| Point::new(x, y)),
+            y.checked_add(T::ONE).map(|y| Point::new(x, y)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// The up to eight adjacent points, including diagonals.
+    pub fn neighbours_with_diagonals(self) -> Vec<Point<T>> {
+        let around = |v: T| vec![v.checked_sub(T::ONE), Some(v), v.checked_add(T::ONE)];
+
+        let mut points = vec![];
+        for y in around(self.y).into_iter().flatten() {
+            for x in around(self.x).into_iter().flatten() {
+                if (x, y) != (self.x, self.y) {
+                    points.push(Point::new(x, y));
+                }
+            }
+        }
+        points
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Coordinate> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Coordinate> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Coordinate> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, factor: T) -> Point<T> {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Coordinate + Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// An axis-aligned rectangle covering `min` up to but not including `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T = i64> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Coordinate> Rect<T> {
+    pub fn new(origin: Point<T>, width: T, height: T) -> Self {
+        Rect {
+            min: origin,
+            max: Point::new(origin.x + width, origin.y + height),
+        }
+    }
+
+    /// The smallest rectangle containing every point, or `None` if there
+    /// are no points.
+    pub fn bounding<I: IntoIterator<Item = Point<T>>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        });
+
+        Some(Rect {
+            min,
+            max: Point::new(max.x + T::ONE, max.y + T::ONE),
+        })
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x.distance(self.min.x)
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y.distance(self.min.y)
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x >= self.max.x || self.min.y >= self.max.y
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.min.x <= point.x && point.x < self.max.x && self.min.y <= point.y && point.y < self.max.y
+    }
+
+    pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+        Rect {
+            min: Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let rect = Rect {
+            min: Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            max: Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        };
+
+        if rect.is_empty() {
+            None
+        } else {
+            Some(rect)
+        }
+    }
+
+    /// Every point in the rectangle, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point<T>> {
+        let Rect { min, max } = *self;
+        let rows = std::iter::successors(Some(min.y), move |&y| Some(y + T::ONE));
+        rows.take_while(move |&y| y < max.y).flat_map(move |y| {
+            std::iter::successors(Some(min.x), move |&x| Some(x + T::ONE))
+                .take_while(move |&x| x < max.x)
+                .map(move |x| Point::new(x, y))
+        })
+    }
+}
+
+/// A dense, bounded grid with a cell for every point from `(0, 0)` up to
+/// its width and height.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Rect<usize> {
+        Rect::new(Point::origin(), self.width, self.height)
+    }
+
+    fn offset(&self, point: Point<usize>) -> Option<usize> {
+        if point.x < self.width && point.y < self.height {
+            Some(point.y * self.width + point.x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, point: Point<usize>) -> Option<&T> {
+        self.offset(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point<usize>) -> Option<&mut T> {
+        self.offset(point).map(move |i| &mut self.cells[i])
+    }
+
+    /// Every cell with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (Point::new(i % width, i / width), cell))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point<usize>, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| (Point::new(i % width, i / width), cell))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// A read-only view of the part of the grid inside `rect`, clipped to
+    /// the grid's bounds.
+    pub fn view(&self, rect: Rect<usize>) -> GridView<'_, T> {
+        let rect = rect.intersection(&self.bounds()).unwrap_or(Rect {
+            min: Point::origin(),
+            max: Point::origin(),
+        });
+        GridView { grid: self, rect }
+    }
+
+    /// The smallest rectangle containing every cell that matches.
+    pub fn bounding_box<F: Fn(&T) -> bool>(&self, matches: F) -> Option<Rect<usize>> {
+        Rect::bounding(self.iter().filter(|(_, cell)| matches(cell)).map(|(p, _)| p))
+    }
+
+    /// Draws the grid one row per line, using `draw` for every cell.
+    pub fn render<F: Fn(&T) -> char>(&self, draw: F) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            out.extend(row.iter().map(&draw));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &T {
+        self.get(point).expect("Point outside of grid")
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut T {
+        self.get_mut(point).expect("Point outside of grid")
+    }
+}
+
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rect: Rect<usize>,
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// The area of the grid covered by the view.
+    pub fn bounds(&self) -> Rect<usize> {
+        self.rect
+    }
+
+    /// Looks up a point relative to the view's top-left corner.
+    pub fn get(&self, point: Point<usize>) -> Option<&'a T> {
+        let absolute = point + self.rect.min;
+        if self.rect.contains(absolute) {
+            self.grid.get(absolute)
+        } else {
+            None
+        }
+    }
+
+    /// Every cell in the view with its point in grid coordinates.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &'a T)> + '_ {
+        let grid = self.grid;
+        self.rect.points().map(move |p| (p, &grid[p]))
+    }
+}
+
+/// A grid that only stores the cells that were set, for unbounded or
+/// mostly empty areas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T, C = i64>
+where
+    C: Coordinate,
+{
+    cells: HashMap<Point<C>, T>,
+}
+
+impl<T, C: Coordinate> Default for SparseGrid<T, C> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T, C: Coordinate> SparseGrid<T, C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, point: Point<C>) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point<C>) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn insert(&mut self, point: Point<C>, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn entry(&mut self, point: Point<C>) -> std::collections::hash_map::Entry<'_, Point<C>, T> {
+        self.cells.entry(point)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point<C>, &T)> {
+        self.cells.iter().map(|(&p, cell)| (p, cell))
+    }
+
+    /// The smallest rectangle containing every set cell.
+    pub fn bounding_box(&self) -> Option<Rect<C>> {
+        Rect::bounding(self.cells.keys().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_computes_manhattan_distances() {
+        assert_eq!(Point::new(1i64, 1).manhattan_distance(Point::new(8, 3)), 9);
+        assert_eq!(Point::new(-2i32, 5).manhattan_distance(Point::new(3, -1)), 11);
+        assert_eq!(Point::new(7u32, 1).manhattan_distance(Point::new(2, 4)), 8)
+    }
+
+    #[test]
+    fn it_does_arithmetic_on_points() {
+        let p = Point::new(1i64, 2);
+
+        assert_eq!(p + Point::new(3, 4), Point::new(4, 6));
+        assert_eq!(p - Point::new(3, 4), Point::new(-2, -2));
+        assert_eq!(p * 3, Point::new(3, 6));
+        assert_eq!(-p, Point::new(-1, -2));
+        assert_eq!(Point::new(u32::MAX, 0).checked_add(Point::new(1, 0)), None)
+    }
+
+    #[test]
+    fn it_leaves_out_neighbours_outside_the_coordinate_type() {
+        assert_eq!(
+            Point::new(0u32, 0).neighbours(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(Point::new(5i64, 5).neighbours().len(), 4);
+        assert_eq!(Point::new(5i64, 5).neighbours_with_diagonals().len(), 8);
+        assert_eq!(Point::new(0usize, 3).neighbours_with_diagonals().len(), 5)
+    }
+
+    #[test]
+    fn it_intersects_rectangles() {
+        let a = Rect::new(Point::new(1u32, 3), 4, 4);
+        let b = Rect::new(Point::new(3u32, 1), 4, 4);
+        let c = Rect::new(Point::new(5u32, 5), 2, 2);
+
+        assert_eq!(a.intersection(&b), Some(Rect::new(Point::new(3, 3), 2, 2)));
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.union(&b), Rect::new(Point::new(1, 1), 6, 6));
+        assert_eq!(c.points().count() as u32, c.area())
+    }
+
+    #[test]
+    fn it_finds_the_bounding_box_of_points() {
+        let points = vec![Point::new(3i64, -1), Point::new(-2, 4), Point::new(0, 0)];
+
+        assert_eq!(
+            Rect::bounding(points),
+            Some(Rect {
+                min: Point::new(-2, -1),
+                max: Point::new(4, 5),
+            })
+        );
+        assert_eq!(Rect::<i64>::bounding(vec![]), None)
+    }
+
+    #[test]
+    fn it_iterates_and_views_a_dense_grid() {
+        let mut grid = Grid::new(4, 3, 0);
+        for (p, cell) in grid.iter_mut() {
+            *cell = p.y * 10 + p.x;
+        }
+
+        assert_eq!(grid[Point::new(2, 1)], 12);
+        assert_eq!(grid.get(Point::new(4, 0)), None);
+
+        let view = grid.view(Rect::new(Point::new(2, 1), 5, 5));
+        assert_eq!(view.bounds(), Rect::new(Point::new(2, 1), 2, 2));
+        assert_eq!(view.get(Point::new(0, 0)), Some(&12));
+        assert_eq!(view.iter().map(|(_, &c)| c).collect::<Vec<_>>(), vec![12, 13, 22, 23])
+    }
+
+    #[test]
+    fn it_finds_the_bounding_box_of_matching_cells() {
+        let mut grid = Grid::new(5, 5, '.');
+        grid[Point::new(1, 3)] = '#';
+        grid[Point::new(3, 1)] = '#';
+
+        assert_eq!(
+            grid.bounding_box(|&c| c == '#'),
+            Some(Rect::new(Point::new(1, 1), 3, 3))
+        );
+        assert_eq!(grid.bounding_box(|&c| c == 'x'), None);
+        assert_eq!(grid.render(|&c| c).lines().nth(3), Some(".#..."))
+    }
+
+    #[test]
+    fn it_stores_only_set_cells_in_a_sparse_grid() {
+        let mut grid: SparseGrid<char> = SparseGrid::new();
+        grid.insert(Point::new(-100, 7), 'a');
+        *grid.entry(Point::new(50, -3)).or_insert('b') = 'c';
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(Point::new(50, -3)), Some(&'c'));
+        assert_eq!(grid.get(Point::new(0, 0)), None);
+        assert_eq!(grid.bounding_box().map(|r| (r.width(), r.height())), Some((151, 11)))
+    }
+}
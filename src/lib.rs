@@ -1,13 +1,20 @@
-extern crate regex;
 extern crate itertools;
+extern crate unicode_segmentation;
 
-mod common;
+#[cfg(test)]
+mod check;
+pub mod common;
+pub mod fuzz;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+#[cfg(test)]
+mod snapshot;
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-// mod day6;
-// mod day7;
-mod day8;
+pub mod params;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+
+pub mod y2018;
@@ -0,0 +1,231 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+
+use advent_of_code::common::read_file_to_string;
+use advent_of_code::generate::{self, Rng};
+use advent_of_code::params::Overrides;
+use advent_of_code::report;
+use advent_of_code::scaffold;
+use advent_of_code::submit::{self, Attempts, Endpoint};
+use advent_of_code::runner;
+use advent_of_code::y2018::day8;
+
+const USAGE: &str = "usage:
+    advent-of-code [<options>] run <day> [<part>]
+    advent-of-code [<options>] run --all [--markdown <file>]
+    advent-of-code [<options>] submit <day> <part> [<answer>]
+    advent-of-code [<options>] verify
+    advent-of-code [--year <year>] new <day>
+    advent-of-code export <dot|json> [<input>]
+    advent-of-code generate <day> [<seed> [<size>]]
+
+options:
+    --year <year>            defaults to 2018
+    --profile <name>         whose inputs and answers to use (not for verify)
+    --config <file>          sets puzzle parameters, one per line
+    --param day<N>.<name>=<value>
+                             sets a puzzle parameter, after the config file
+
+export and generate work on the 2018 puzzles. The default profile's inputs
+and answers are in input and answers, any other's in input.<name> and
+answers.<name>. The parameters are day1.start (default 0), day4.window
+(minutes of the day, default 0-59) and day5.alphabet (a to z).
+submit posts to $AOC_ENDPOINT (default https://adventofcode.com) with the
+session cookie in $AOC_SESSION, through curl for https endpoints. Without
+an answer, the computed one is sent.
+run --all solves every day, compares the answers with the verified ones,
+and fails if any differ. --markdown also writes the table to a file.
+verify does the same for every profile, and names the ones that fail.
+new creates the day's module and an empty input, and registers them.";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+
+    let year = take_option(&mut args, "--year").map_or(runner::DEFAULT_YEAR, parse_number);
+    let profile = take_option(&mut args, "--profile").unwrap_or(runner::DEFAULT_PROFILE);
+    if !runner::is_profile(profile) {
+        usage();
+    }
+
+    let mut overrides = match take_option(&mut args, "--config") {
+        Some(path) => Overrides::load(path).unwrap_or_else(|error| fail(&error)),
+        None => Overrides::default(),
+    };
+    while let Some(assignment) = take_option(&mut args, "--param") {
+        overrides.set(assignment).unwrap_or_else(|error| fail(&error));
+    }
+    let overrides = &overrides;
+
+    match args.as_slice() {
+        ["run", "--all"] => run_all(year, profile, overrides, None),
+        ["run", "--all", "--markdown", path] => run_all(year, profile, overrides, Some(path)),
+        ["run", day] => run(year, profile, overrides, parse_number(day), &[1, 2]),
+        ["run", day, part] => {
+            run(year, profile, overrides, parse_number(day), &[parse_number(part)])
+        }
+        ["verify"] => verify(year, overrides),
+        ["export", format] => export(format, &runner::input_path(2018, 8, profile)),
+        ["export", format, path] => export(format, path),
+        ["generate", day] => generate(parse_number(day), 0, None),
+        ["generate", day, seed] => generate(parse_number(day), parse_number(seed), None),
+        ["generate", day, seed, size] => {
+            generate(parse_number(day), parse_number(seed), Some(parse_number(size)))
+        }
+        ["submit", day, part] => {
+            submit(year, profile, overrides, parse_number(day), parse_number(part), None)
+        }
+        ["submit", day, part, answer] => {
+            submit(year, profile, overrides, parse_number(day), parse_number(part), Some(answer))
+        }
+        ["new", day] => new(year, parse_number(day)),
+        _ => usage(),
+    }
+}
+
+/// Removes `--name <value>` from anywhere in `args`, and returns the value.
+fn take_option<'a>(args: &mut Vec<&'a str>, name: &str) -> Option<&'a str> {
+    let index = args.iter().position(|&arg| arg == name)?;
+    if index + 1 == args.len() {
+        usage();
+    }
+    let value = args[index + 1];
+    args.drain(index..index + 2);
+    Some(value)
+}
+
+fn run(year: u32, profile: &str, overrides: &Overrides, day: u32, parts: &[u32]) {
+    let attempts = Attempts::load(&submit::attempts_path(year, day, profile));
+    if let Err(error) = &attempts {
+        eprintln!("ignoring attempts: {}", error);
+    }
+
+    for &part in parts {
+        match runner::solve_with(year, day, part, profile, overrides) {
+            Ok(answer) => match &attempts {
+                Ok(attempts) => println!(
+                    "day {} part {}: {} ({})",
+                    day,
+                    part,
+                    answer,
+                    attempts.assess(part, &answer)
+                ),
+                Err(_) => println!("day {} part {}: {}", day, part, answer),
+            },
+            Err(error) => {
+                eprintln!("day {} part {}: {}", day, part, error);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn run_all(year: u32, profile: &str, overrides: &Overrides, markdown: Option<&str>) {
+    let report = report::run_all(year, profile, overrides).unwrap_or_else(|error| fail(&error));
+    print!("{}", report.to_table());
+
+    if let Some(path) = markdown {
+        fs::write(path, report.to_markdown()).unwrap_or_else(|error| fail(&error));
+    }
+    if report.mismatches() > 0 {
+        process::exit(1);
+    }
+}
+
+fn verify(year: u32, overrides: &Overrides) {
+    let reports = report::verify(year, overrides).unwrap_or_else(|error| fail(&error));
+
+    for report in &reports {
+        println!("{}: {}", report.profile, report.summary());
+        for row in report.mismatched() {
+            match (&row.answer, &row.status) {
+                (Ok(answer), report::Status::Mismatch { expected }) => println!(
+                    "  day {} part {}: {}, expected {}",
+                    row.day, row.part, answer, expected
+                ),
+                (Err(error), _) => println!("  day {} part {}: {}", row.day, row.part, error),
+                _ => {}
+            }
+        }
+    }
+
+    let failing: Vec<&str> = reports
+        .iter()
+        .filter(|report| report.mismatches() > 0)
+        .map(|report| report.profile.as_str())
+        .collect();
+    if !failing.is_empty() {
+        fail(&format!("failing profiles: {}", failing.join(", ")));
+    }
+}
+
+fn export(format: &str, path: &str) {
+    let tree = day8::try_parse_line(&read_file_to_string(path)).unwrap_or_else(|error| fail(&error));
+
+    let exported = match format {
+        "dot" => day8::to_dot(&tree),
+        "json" => day8::to_json(&tree).map(|json| json + "\n"),
+        _ => usage(),
+    };
+    print!("{}", exported.unwrap_or_else(|error| fail(&error)));
+}
+
+fn generate(day: u32, seed: u64, size: Option<usize>) {
+    match generate::input(day, &mut Rng::new(seed), size) {
+        Some(input) => println!("{}", input),
+        None => {
+            eprintln!("no generator for day {}", day);
+            process::exit(1);
+        }
+    }
+}
+
+fn submit(
+    year: u32,
+    profile: &str,
+    overrides: &Overrides,
+    day: u32,
+    part: u32,
+    answer: Option<&str>,
+) {
+    let answer = match answer {
+        Some(answer) => answer.to_string(),
+        None => runner::solve_with(year, day, part, profile, overrides)
+            .unwrap_or_else(|error| fail(&error)),
+    };
+    let attempts_path = submit::attempts_path(year, day, profile);
+    let mut attempts = Attempts::load(&attempts_path).unwrap_or_else(|error| fail(&error));
+
+    match submit::submit(&Endpoint::from_env(), year, day, part, &answer, &mut attempts) {
+        Ok(verdict) => println!("day {} part {}: {} is {}", day, part, answer, verdict),
+        Err(error) => fail(&error),
+    }
+}
+
+fn new(year: u32, day: u32) {
+    match scaffold::new_day(Path::new("."), year, day) {
+        Ok(created) => {
+            for path in created {
+                println!("created {}", path.display());
+            }
+        }
+        Err(error) => fail(&error),
+    }
+}
+
+fn fail(error: &dyn std::fmt::Display) -> ! {
+    eprintln!("{}", error);
+    process::exit(1)
+}
+
+fn parse_number<T: FromStr>(arg: &str) -> T {
+    arg.parse().unwrap_or_else(|_| usage())
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2)
+}
@@ -0,0 +1,183 @@
+//! Overriding the parameters of a day's puzzle.
+//!
+//! Days whose puzzle has constants worth changing expose them as a typed
+//! `Params` struct, whose `Default` is the puzzle as published. Overrides
+//! come from a config file or the command line as `day<N>.<name> = <value>`,
+//! and are applied to the defaults when the day is solved:
+//!
+//! ```text
+//! # Start from frequency 100 and only count sleep before 00:30.
+//! day1.start = 100
+//! day4.window = 0-29
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+/// The parameters of a day, which start out as the puzzle's own.
+pub trait Params: Default {
+    /// Sets the parameter `name` from its text, or explains why it cannot.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String>;
+}
+
+/// Days without parameters.
+impl Params for () {
+    fn set(&mut self, _: &str, _: &str) -> Result<(), String> {
+        Err(unknown())
+    }
+}
+
+/// Why a parameter was not set, for the `Params` of each day.
+pub fn unknown() -> String {
+    "there is no such parameter".to_string()
+}
+
+/// Parses a value that is a plain number, or other `FromStr` type.
+pub fn parse_value<T: FromStr>(value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{:?} is not a valid value", value))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    Io(String),
+    /// A line is not of the form `day<N>.<name> = <value>`.
+    Syntax(String),
+    /// The day rejected the value, or has no such parameter.
+    Invalid { day: u32, name: String, reason: String },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamError::Io(e) => write!(f, "cannot read parameters: {}", e),
+            ParamError::Syntax(e) => write!(f, "not a parameter: {}", e),
+            ParamError::Invalid { day, name, reason } => {
+                write!(f, "day{}.{}: {}", day, name, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Values for the parameters of each day, in the order they were given.
+/// Later values win.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overrides {
+    values: BTreeMap<u32, Vec<(String, String)>>,
+}
+
+impl Overrides {
+    /// Reads a config file with one assignment per line. Blank lines and
+    /// lines starting with `#` are skipped.
+    pub fn load(path: &str) -> Result<Self, ParamError> {
+        let text = fs::read_to_string(path).map_err(|e| ParamError::Io(format!("{}: {}", path, e)))?;
+        let mut overrides = Overrides::default();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            overrides.set(line).map_err(|error| match error {
+                ParamError::Syntax(e) => ParamError::Syntax(format!("{}, line {}: {}", path, index + 1, e)),
+                error => error,
+            })?;
+        }
+
+        Ok(overrides)
+    }
+
+    /// Adds one assignment, such as `day1.start=100`.
+    pub fn set(&mut self, assignment: &str) -> Result<(), ParamError> {
+        let syntax = || ParamError::Syntax(format!("{:?}", assignment));
+
+        let (key, value) = assignment.split_once('=').ok_or_else(syntax)?;
+        let (day, name) = key.trim().split_once('.').ok_or_else(syntax)?;
+        let day = day.strip_prefix("day").and_then(|d| d.parse().ok()).ok_or_else(syntax)?;
+        if name.is_empty() {
+            return Err(syntax());
+        }
+
+        self.values
+            .entry(day)
+            .or_default()
+            .push((name.to_string(), value.trim().to_string()));
+        Ok(())
+    }
+
+    /// The parameters of `day`: its defaults, with the overrides applied.
+    pub fn resolve<P: Params>(&self, day: u32) -> Result<P, ParamError> {
+        let mut params = P::default();
+
+        for (name, value) in self.values.get(&day).into_iter().flatten() {
+            params.set(name, value).map_err(|reason| ParamError::Invalid {
+                day,
+                name: name.clone(),
+                reason,
+            })?;
+        }
+
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Speed {
+        speed: u32,
+    }
+
+    impl Params for Speed {
+        fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+            match name {
+                "speed" => self.speed = parse_value(value)?,
+                _ => return Err(unknown()),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn it_applies_overrides_in_order() {
+        let mut overrides = Overrides::default();
+        overrides.set("day3.speed = 4").unwrap();
+        overrides.set("day3.speed=7").unwrap();
+
+        assert_eq!(overrides.resolve::<Speed>(3), Ok(Speed { speed: 7 }));
+        assert_eq!(overrides.resolve::<Speed>(4), Ok(Speed::default()));
+        assert_eq!(overrides.resolve::<()>(4), Ok(()))
+    }
+
+    #[test]
+    fn it_rejects_bad_overrides() {
+        let mut overrides = Overrides::default();
+        for assignment in ["speed=4", "day3=4", "day3.speed", "dayx.speed=4", "day3.=4"] {
+            assert!(matches!(overrides.set(assignment), Err(ParamError::Syntax(_))), "{}", assignment);
+        }
+
+        overrides.set("day3.speed=fast").unwrap();
+        assert_eq!(overrides.resolve::<Speed>(3).unwrap_err().to_string(), "day3.speed: \"fast\" is not a valid value");
+        assert_eq!(overrides.resolve::<()>(3).unwrap_err().to_string(), "day3.speed: there is no such parameter")
+    }
+
+    #[test]
+    fn it_loads_a_config_file() {
+        let path = std::env::temp_dir().join(format!("aoc-params-{}", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        fs::write(path, "# Faster\n\nday3.speed = 9\n").unwrap();
+        assert_eq!(Overrides::load(path).unwrap().resolve::<Speed>(3), Ok(Speed { speed: 9 }));
+
+        fs::write(path, "day3.speed = 9\nspeed = 9\n").unwrap();
+        assert_eq!(Overrides::load(path).unwrap_err(), ParamError::Syntax(format!("{}, line 2: \"speed = 9\"", path)));
+
+        fs::remove_file(path).unwrap();
+        assert!(matches!(Overrides::load(path), Err(ParamError::Io(_))))
+    }
+}
@@ -0,0 +1,240 @@
+//! Solving every registered day of a year at once, and reporting the
+//! answers, how long each took, and whether they match the verified ones
+//! of a profile.
+
+use std::fmt::Write;
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::params::Overrides;
+use crate::runner::{self, SolveError};
+
+/// How an answer compares with the verified one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Verified,
+    /// The answer differs from the verified one, or solving failed.
+    Mismatch { expected: String },
+    /// There is no verified answer to compare with.
+    Unverified,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Result<String, SolveError>,
+    pub elapsed: Duration,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub year: u32,
+    pub profile: String,
+    pub rows: Vec<Row>,
+}
+
+/// Solves both parts of every day of `year` from the inputs of `profile`,
+/// in order, timing each.
+pub fn run_all(year: u32, profile: &str, overrides: &Overrides) -> io::Result<Report> {
+    let answers = runner::load_answers(year, profile)?;
+    let mut rows = Vec::new();
+
+    for day in runner::days(year) {
+        for part in 1..=2 {
+            let start = Instant::now();
+            let answer = runner::solve_with(year, day, part, profile, overrides);
+            let elapsed = start.elapsed();
+
+            let status = match answers.get(&(day, part)) {
+                Some(expected) if answer.as_ref() == Ok(expected) => Status::Verified,
+                Some(expected) => Status::Mismatch { expected: expected.clone() },
+                None => Status::Unverified,
+            };
+            rows.push(Row { day, part, answer, elapsed, status });
+        }
+    }
+
+    Ok(Report { year, profile: profile.to_string(), rows })
+}
+
+/// Runs every day for each profile of `year`.
+pub fn verify(year: u32, overrides: &Overrides) -> io::Result<Vec<Report>> {
+    runner::profiles(year)?
+        .iter()
+        .map(|profile| run_all(year, profile, overrides))
+        .collect()
+}
+
+impl Row {
+    fn cells(&self) -> [String; 5] {
+        let answer = match &self.answer {
+            Ok(answer) => answer.clone(),
+            Err(error) => format!("error: {}", error),
+        };
+        let status = match &self.status {
+            Status::Verified => "verified".to_string(),
+            Status::Mismatch { expected } => format!("MISMATCH, expected {}", expected),
+            Status::Unverified => "unverified".to_string(),
+        };
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            answer,
+            format!("{:.1?}", self.elapsed),
+            status,
+        ]
+    }
+}
+
+impl Report {
+    const HEADER: [&'static str; 5] = ["day", "part", "answer", "time", "status"];
+
+    pub fn mismatches(&self) -> usize {
+        self.mismatched().count()
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.rows.iter().map(|row| row.elapsed).sum()
+    }
+
+    pub fn mismatched(&self) -> impl Iterator<Item = &Row> {
+        self.rows
+            .iter()
+            .filter(|row| matches!(row.status, Status::Mismatch { .. }))
+    }
+
+    pub fn summary(&self) -> String {
+        let verified = self.rows.iter().filter(|row| row.status == Status::Verified).count();
+        format!(
+            "{} answers in {:.1?}: {} verified, {} mismatched",
+            self.rows.len(),
+            self.total_time(),
+            verified,
+            self.mismatches()
+        )
+    }
+
+    /// A plain text table with aligned columns, followed by a summary.
+    pub fn to_table(&self) -> String {
+        let rows: Vec<[String; 5]> = self.rows.iter().map(Row::cells).collect();
+        let mut widths = Self::HEADER.map(str::len);
+        for cells in &rows {
+            for (width, cell) in widths.iter_mut().zip(cells) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut out = String::new();
+        let header = Self::HEADER.map(String::from);
+        for cells in std::iter::once(&header).chain(&rows) {
+            let line: Vec<String> = cells
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<1$}", cell, width))
+                .collect();
+            writeln!(out, "{}", line.join("  ").trim_end()).unwrap();
+        }
+        writeln!(out, "\n{}", self.summary()).unwrap();
+        out
+    }
+
+    /// The same table as Markdown, under a heading for the year and any
+    /// profile other than the default one.
+    pub fn to_markdown(&self) -> String {
+        let mut out = if self.profile == runner::DEFAULT_PROFILE {
+            format!("## {}\n\n", self.year)
+        } else {
+            format!("## {} ({})\n\n", self.year, self.profile)
+        };
+        writeln!(out, "| {} |", Self::HEADER.join(" | ")).unwrap();
+        writeln!(out, "|{}", "---|".repeat(Self::HEADER.len())).unwrap();
+        for row in &self.rows {
+            let cells: Vec<String> = row.cells().iter().map(|cell| cell.replace('|', "\\|")).collect();
+            writeln!(out, "| {} |", cells.join(" | ")).unwrap();
+        }
+        writeln!(out, "\n{}", self.summary()).unwrap();
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> Report {
+        let row = |day, part, answer: Result<&str, SolveError>, millis, status| Row {
+            day,
+            part,
+            answer: answer.map(String::from),
+            elapsed: Duration::from_millis(millis),
+            status,
+        };
+        Report {
+            year: 2018,
+            profile: runner::DEFAULT_PROFILE.to_string(),
+            rows: vec![
+                row(1, 1, Ok("470"), 2, Status::Verified),
+                row(1, 2, Ok("791"), 15, Status::Mismatch { expected: "790".to_string() }),
+                row(2, 1, Err(SolveError::NoAnswer), 1, Status::Unverified),
+            ],
+        }
+    }
+
+    #[test]
+    fn it_runs_every_day_against_the_verified_answers() {
+        let report = run_all(2018, runner::DEFAULT_PROFILE, &Overrides::default()).unwrap();
+        let answers = runner::load_answers(2018, runner::DEFAULT_PROFILE).unwrap();
+
+        assert_eq!(report.rows.len(), 2 * runner::days(2018).len());
+        for row in &report.rows {
+            let verified = answers.contains_key(&(row.day, row.part));
+            assert_eq!(row.status == Status::Verified, verified, "day {} part {}", row.day, row.part);
+        }
+        assert_eq!(report.mismatches(), 0)
+    }
+
+    #[test]
+    fn it_verifies_every_profile() {
+        let reports = verify(2018, &Overrides::default()).unwrap();
+
+        let profiles: Vec<&str> = reports.iter().map(|r| r.profile.as_str()).collect();
+        assert_eq!(profiles, vec!["default", "example"]);
+        assert!(reports.iter().all(|report| report.mismatches() == 0));
+        assert!(reports[1].to_markdown().starts_with("## 2018 (example)\n"))
+    }
+
+    #[test]
+    fn it_prints_a_table() {
+        assert_eq!(
+            report().to_table(),
+            "\
+day  part  answer                          time    status
+1    1     470                             2.0ms   verified
+1    2     791                             15.0ms  MISMATCH, expected 790
+2    1     error: the input has no answer  1.0ms   unverified
+
+3 answers in 18.0ms: 1 verified, 1 mismatched
+"
+        )
+    }
+
+    #[test]
+    fn it_writes_markdown() {
+        assert_eq!(
+            report().to_markdown(),
+            "\
+## 2018
+
+| day | part | answer | time | status |
+|---|---|---|---|---|
+| 1 | 1 | 470 | 2.0ms | verified |
+| 1 | 2 | 791 | 15.0ms | MISMATCH, expected 790 |
+| 2 | 1 | error: the input has no answer | 1.0ms | unverified |
+
+3 answers in 18.0ms: 1 verified, 1 mismatched
+"
+        )
+    }
+}
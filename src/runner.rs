@@ -0,0 +1,301 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::io;
+
+use crate::common::parse::ParseError;
+use crate::params::{Overrides, ParamError};
+use crate::y2018;
+
+/// The year commands use unless told otherwise.
+pub const DEFAULT_YEAR: u32 = 2018;
+
+/// The profile whose inputs and answers have no suffix. Every other
+/// profile keeps its own in `input.<profile>` and `answers.<profile>`
+/// next to them.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The solutions of each year.
+const YEARS: &[(u32, &[Solution])] = &[
+    (2018, y2018::SOLUTIONS),
+];
+
+/// Solves either part of one day from the text of its input, with the
+/// day's parameters overridden. The part is always 1 or 2.
+pub struct Solution {
+    pub day: u32,
+    pub solve: fn(u32, &str, &Overrides) -> Result<String, SolveError>,
+}
+
+pub fn years() -> impl Iterator<Item = u32> {
+    YEARS.iter().map(|&(year, _)| year)
+}
+
+/// The solutions of `year`, in order of day. Unknown years have none.
+pub fn solutions(year: u32) -> &'static [Solution] {
+    YEARS
+        .iter()
+        .find(|&&(y, _)| y == year)
+        .map_or(&[], |&(_, solutions)| solutions)
+}
+
+pub fn find(year: u32, day: u32) -> Option<&'static Solution> {
+    solutions(year).iter().find(|s| s.day == day)
+}
+
+pub fn days(year: u32) -> Vec<u32> {
+    solutions(year).iter().map(|s| s.day).collect()
+}
+
+/// Whether `name` can name a profile: a non-empty word of letters,
+/// digits, `-` and `_`.
+pub fn is_profile(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The file of `profile` that corresponds to `path` of the default one.
+pub fn with_profile(path: String, profile: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        path
+    } else {
+        format!("{}.{}", path, profile)
+    }
+}
+
+pub fn input_path(year: u32, day: u32, profile: &str) -> String {
+    with_profile(format!("./src/y{}/day{}/input", year, day), profile)
+}
+
+/// The file of verified answers for a year, with one `<day> <part>
+/// <answer>` line per answer.
+pub fn answers_path(year: u32, profile: &str) -> String {
+    with_profile(format!("./src/y{}/answers", year), profile)
+}
+
+/// The profiles of `year`: the default one, then every profile with
+/// answers or an input for one of the days, in order of name.
+pub fn profiles(year: u32) -> io::Result<Vec<String>> {
+    let mut dirs = vec![format!("./src/y{}", year)];
+    dirs.extend(days(year).iter().map(|day| format!("./src/y{}/day{}", year, day)));
+
+    let mut profiles = BTreeSet::new();
+    for dir in dirs {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let name = entry?.file_name().to_string_lossy().into_owned();
+            let profile = name.strip_prefix("answers.").or(name.strip_prefix("input."));
+            if let Some(profile) = profile.filter(|&p| is_profile(p)) {
+                profiles.insert(profile.to_string());
+            }
+        }
+    }
+    profiles.remove(DEFAULT_PROFILE);
+
+    let mut all = vec![DEFAULT_PROFILE.to_string()];
+    all.extend(profiles);
+    Ok(all)
+}
+
+/// The verified answers of `year` for `profile`, keyed by day and part. A
+/// missing file has none.
+pub fn load_answers(year: u32, profile: &str) -> io::Result<BTreeMap<(u32, u32), String>> {
+    let path = answers_path(year, profile);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [day, part, answer] => match (day.parse(), part.parse()) {
+                    (Ok(day), Ok(part)) => Ok(((day, part), answer.to_string())),
+                    _ => Err(index),
+                },
+                _ => Err(index),
+            }
+        })
+        .collect::<Result<_, _>>()
+        .map_err(|index| {
+            let message = format!("{}, line {}: not an answer", path, index + 1);
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// There is no solution registered for the day and part.
+    Unknown { year: u32, day: u32, part: u32 },
+    Io(String),
+    Parse(ParseError),
+    Param(ParamError),
+    /// The input parses but is not a valid puzzle, e.g. it is too large
+    /// for the solver.
+    Invalid(String),
+    /// The input is valid but has no answer.
+    NoAnswer,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Unknown { year, day, part } => {
+                write!(f, "no solution for {} day {} part {}", year, day, part)
+            }
+            SolveError::Io(e) => write!(f, "cannot read input: {}", e),
+            SolveError::Parse(e) => write!(f, "{}", e),
+            SolveError::Param(e) => write!(f, "{}", e),
+            SolveError::Invalid(e) => write!(f, "invalid input: {}", e),
+            SolveError::NoAnswer => write!(f, "the input has no answer"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<ParseError> for SolveError {
+    fn from(error: ParseError) -> Self {
+        SolveError::Parse(error)
+    }
+}
+
+impl From<ParamError> for SolveError {
+    fn from(error: ParamError) -> Self {
+        SolveError::Param(error)
+    }
+}
+
+/// Solves one part of a day against the input file of `profile`.
+pub fn solve(year: u32, day: u32, part: u32, profile: &str) -> Result<String, SolveError> {
+    solve_with(year, day, part, profile, &Overrides::default())
+}
+
+/// Like `solve`, with the day's parameters overridden.
+pub fn solve_with(
+    year: u32,
+    day: u32,
+    part: u32,
+    profile: &str,
+    overrides: &Overrides,
+) -> Result<String, SolveError> {
+    if find(year, day).is_none() {
+        return Err(SolveError::Unknown { year, day, part });
+    }
+    let input = fs::read_to_string(input_path(year, day, profile)).map_err(|e| SolveError::Io(e.to_string()))?;
+
+    solve_input_with(year, day, part, &input, overrides)
+}
+
+/// Solves one part of a day against the given input. This never panics,
+/// whatever the input.
+pub fn solve_input(year: u32, day: u32, part: u32, input: &str) -> Result<String, SolveError> {
+    solve_input_with(year, day, part, input, &Overrides::default())
+}
+
+/// Like `solve_input`, with the day's parameters overridden.
+pub fn solve_input_with(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: &str,
+    overrides: &Overrides,
+) -> Result<String, SolveError> {
+    match find(year, day) {
+        Some(solution) if part == 1 || part == 2 => (solution.solve)(part, input, overrides),
+        _ => Err(SolveError::Unknown { year, day, part }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_solves_the_examples() {
+        assert_eq!(solve_input(2018, 1, 2, "+3\n+3\n+4\n-2\n-4\n"), Ok("10".to_string()));
+        assert_eq!(solve_input(2018, 5, 1, "dabAcCaCBAcCcaDA"), Ok("10".to_string()));
+        assert_eq!(solve_input(2018, 8, 2, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2"), Ok("66".to_string()))
+    }
+
+    #[test]
+    fn it_reports_why_there_is_no_answer() {
+        assert_eq!(solve_input(2018, 1, 2, "+1\n+1\n"), Err(SolveError::NoAnswer));
+        assert_eq!(solve_input(2018, 3, 1, "#1 @ 0,0: 2x2\n#2 @ 4294967295,0: 1x1"), Err(SolveError::Invalid("the fabric is too large".to_string())));
+        assert_eq!(solve_input(2018, 4, 1, "[1518-11-01 00:25] wakes up").unwrap_err().to_string(), "invalid input: wake-up at 11-01 00:25 before any guard began a shift");
+        assert_eq!(solve_input(2018, 8, 1, "1 1").unwrap_err().to_string(), "invalid input: input ends inside a node");
+        assert!(matches!(solve_input(2018, 1, 1, "+1\nx"), Err(SolveError::Parse(ParseError { line: 2, .. }))));
+        assert_eq!(solve_input(2018, 26, 1, ""), Err(SolveError::Unknown { year: 2018, day: 26, part: 1 }));
+        assert_eq!(solve_input(2018, 1, 3, ""), Err(SolveError::Unknown { year: 2018, day: 1, part: 3 }));
+        assert_eq!(solve_input(2015, 1, 1, ""), Err(SolveError::Unknown { year: 2015, day: 1, part: 1 }))
+    }
+
+    #[test]
+    fn it_overrides_the_puzzle_parameters() {
+        let overrides = |assignments: &[&str]| {
+            let mut overrides = Overrides::default();
+            for assignment in assignments {
+                overrides.set(assignment).unwrap();
+            }
+            overrides
+        };
+        let start = overrides(&["day1.start = 100"]);
+
+        assert_eq!(solve_input_with(2018, 1, 1, "+1\n-2\n+3\n+1\n", &start), Ok("103".to_string()));
+        assert_eq!(solve_input_with(2018, 1, 2, "+3\n+3\n+4\n-2\n-4\n", &start), Ok("110".to_string()));
+        assert_eq!(
+            solve_input_with(2018, 1, 1, "+1", &overrides(&["day1.start = 9223372036854775807"])),
+            Err(SolveError::Invalid("the frequencies are too large".to_string()))
+        );
+        assert_eq!(solve_input_with(2018, 5, 1, "aαΑA", &overrides(&["day5.alphabet = aα"])), Ok("0".to_string()));
+        assert_eq!(
+            solve_input_with(2018, 2, 1, "abc", &overrides(&["day2.start = 1"])).unwrap_err().to_string(),
+            "day2.start: there is no such parameter"
+        );
+        assert_eq!(solve_input_with(2018, 3, 1, "#1 @ 0,0: 1x1", &start), Ok("0".to_string()))
+    }
+
+    #[test]
+    fn it_registers_solutions_by_year_and_day() {
+        // Other days and years come and go with the scaffold, so only check
+        // what always holds: the verified days are registered, in order.
+        assert!(years().any(|year| year == 2018));
+        let days = days(2018);
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+        for (day, _) in load_answers(2018, DEFAULT_PROFILE).unwrap().keys() {
+            assert_eq!(find(2018, *day).map(|s| s.day), Some(*day));
+        }
+        assert!(find(2018, 26).is_none());
+        assert!(solutions(2014).is_empty())
+    }
+
+    #[test]
+    fn it_solves_every_day_to_its_verified_answer() {
+        for year in years() {
+            for profile in profiles(year).unwrap() {
+                for ((day, part), expected) in load_answers(year, &profile).unwrap() {
+                    let answer = solve(year, day, part, &profile).unwrap();
+                    assert_eq!(answer, expected, "{} day {} part {} for {}", year, day, part, profile);
+                }
+            }
+        }
+        assert_eq!(load_answers(2018, DEFAULT_PROFILE).unwrap().len(), 12)
+    }
+
+    #[test]
+    fn it_keeps_each_profile_next_to_the_default_one() {
+        assert_eq!(input_path(2018, 3, DEFAULT_PROFILE), "./src/y2018/day3/input");
+        assert_eq!(input_path(2018, 3, "example"), "./src/y2018/day3/input.example");
+        assert_eq!(answers_path(2018, "example"), "./src/y2018/answers.example");
+        assert_eq!(profiles(2018).unwrap(), vec!["default", "example"]);
+        assert_eq!(profiles(2015).unwrap(), vec!["default"]);
+        assert!(is_profile("ann_2") && !is_profile("") && !is_profile("../x"))
+    }
+}
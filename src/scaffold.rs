@@ -0,0 +1,318 @@
+//! Generating the module for a new day.
+//!
+//! `new_day` writes `src/y<year>/day<day>/mod.rs` from a template in the
+//! style of the other days, with an empty `input` next to it, and
+//! registers the module and its solution with the year. A year that does
+//! not exist yet gets its own module, declared in `lib.rs` and listed in
+//! the runner.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Creates and registers the module for `day` of `year` in the crate at
+/// `root`, and returns the files created.
+pub fn new_day(root: &Path, year: u32, day: u32) -> io::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("there is no day {}", day)));
+    }
+
+    let year_dir = root.join(format!("src/y{}", year));
+    let day_dir = year_dir.join(format!("day{}", day));
+    if day_dir.exists() {
+        let message = format!("{} already exists", day_dir.display());
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, message));
+    }
+
+    let mut created = Vec::new();
+    if !year_dir.join("mod.rs").exists() {
+        fs::create_dir_all(&year_dir)?;
+        fs::write(year_dir.join("mod.rs"), year_template(year))?;
+        register_year(root, year)?;
+        created.push(year_dir.join("mod.rs"));
+    }
+
+    fs::create_dir_all(&day_dir)?;
+    fs::write(day_dir.join("mod.rs"), day_template(year, day))?;
+    fs::write(day_dir.join("input"), "")?;
+    created.push(day_dir.join("mod.rs"));
+    created.push(day_dir.join("input"));
+
+    register_day(&year_dir.join("mod.rs"), day)?;
+
+    Ok(created)
+}
+
+fn year_template(year: u32) -> String {
+    format!(
+        "//! The {} puzzles, and how the runner solves each day from its input.
+
+use crate::params::Overrides;
+use crate::runner::{{Solution, SolveError}};
+
+pub const SOLUTIONS: &[Solution] = &[
+];
+",
+        year
+    )
+}
+
+/// A day that is not solved yet: both parts answer 0, and the tests on
+/// the input check that placeholder until there are answers to check.
+fn day_template(year: u32, day: u32) -> String {
+    format!(
+        "pub fn solve1(_lines: Vec<&str>) -> u32 {{
+    0
+}}
+
+pub fn solve2(_lines: Vec<&str>) -> u32 {{
+    0
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use crate::common::*;
+
+    #[test]
+    fn it_works_on_the_input() {{
+        let strings = map_lines_to_strings(\"./src/y{year}/day{day}/input\");
+        let lines = strings.iter().map(|s| s.as_str()).collect();
+
+        assert_eq!(solve1(lines), 0)
+    }}
+
+    #[test]
+    fn it_works_on_the_input_for_star_2() {{
+        let strings = map_lines_to_strings(\"./src/y{year}/day{day}/input\");
+        let lines = strings.iter().map(|s| s.as_str()).collect();
+
+        assert_eq!(solve2(lines), 0)
+    }}
+}}
+",
+        year = year,
+        day = day
+    )
+}
+
+fn adapter_template(day: u32) -> String {
+    format!(
+        "
+fn solve_day{day}(part: u32, input: &str, overrides: &Overrides) -> Result<String, SolveError> {{
+    overrides.resolve::<()>({day})?;
+    let lines: Vec<&str> = input.lines().collect();
+
+    let answer = if part == 1 {{
+        day{day}::solve1(lines)
+    }} else {{
+        day{day}::solve2(lines)
+    }};
+    Ok(answer.to_string())
+}}
+",
+        day = day
+    )
+}
+
+/// Declares the year's module in `lib.rs` and lists its solutions in the
+/// runner.
+fn register_year(root: &Path, year: u32) -> io::Result<()> {
+    let year_key = |line: &str, prefix: &str, suffix: &str| {
+        line.strip_prefix(prefix)?.strip_suffix(suffix)?.parse().ok()
+    };
+
+    edit(&root.join("src/lib.rs"), |text| {
+        let line = format!("pub mod y{};", year);
+        Ok(insert_sorted(text, year, &line, |l| year_key(l, "pub mod y", ";"))
+            .unwrap_or_else(|| format!("{}\n{}\n", text.trim_end(), line)))
+    })?;
+
+    edit(&root.join("src/runner.rs"), |text| {
+        let import = format!("use crate::y{};", year);
+        let text = insert_sorted(text, year, &import, |l| year_key(l, "use crate::y", ";"))
+            .ok_or_else(|| missing("use crate::y<year>;", "runner.rs"))?;
+        let entry = format!("    ({}, y{}::SOLUTIONS),", year, year);
+        insert_sorted(&text, year, &entry, |l| {
+            l.strip_prefix("    (")?.split(',').next()?.parse().ok()
+        })
+        .ok_or_else(|| missing("the YEARS table", "runner.rs"))
+    })
+}
+
+/// Declares the day's module and lists its solution, in order of day.
+fn register_day(year_mod: &Path, day: u32) -> io::Result<()> {
+    edit(year_mod, |text| {
+        let placeholder = format!("// mod day{};\n", day);
+        let text = text.replacen(&placeholder, "", 1);
+
+        let declaration = format!("pub mod day{};", day);
+        let day_key = |line: &str| {
+            let name = line.strip_prefix("pub mod day").or(line.strip_prefix("// mod day"))?;
+            name.strip_suffix(';')?.parse().ok()
+        };
+        let text = insert_sorted(&text, day, &declaration, day_key).unwrap_or_else(|| {
+            let first_use = text.find("\nuse ").map_or(0, |i| i + 1);
+            format!("{}{}\n\n{}", &text[..first_use], declaration, &text[first_use..])
+        });
+
+        let entry = format!("    Solution {{ day: {}, solve: solve_day{} }},", day, day);
+        let text = insert_sorted(&text, day, &entry, |line| {
+            line.strip_prefix("    Solution { day: ")?.split(',').next()?.parse().ok()
+        })
+        .or_else(|| {
+            let table = "pub const SOLUTIONS: &[Solution] = &[\n";
+            let at = text.find(table)? + table.len();
+            Some(format!("{}{}\n{}", &text[..at], entry, &text[at..]))
+        })
+        .ok_or_else(|| missing("the SOLUTIONS table", "the year module"))?;
+
+        Ok(format!("{}{}", text, adapter_template(day)))
+    })
+}
+
+/// Inserts `line` among the lines that `key` recognises, before the first
+/// one with a greater key. Returns `None` if no line is recognised.
+fn insert_sorted<F>(text: &str, value: u32, line: &str, key: F) -> Option<String>
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let lines: Vec<&str> = text.lines().collect();
+    let keyed: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| key(l).map(|k| (i, k)))
+        .collect();
+
+    let &(last, _) = keyed.last()?;
+    let at = keyed
+        .iter()
+        .find(|&&(_, k)| k > value)
+        .map_or(last + 1, |&(i, _)| i);
+
+    let mut lines = lines;
+    lines.insert(at, line);
+    Some(lines.join("\n") + "\n")
+}
+
+fn edit<F>(path: &Path, change: F) -> io::Result<()>
+where
+    F: FnOnce(&str) -> io::Result<String>,
+{
+    let text = fs::read_to_string(path)?;
+    fs::write(path, change(&text)?)
+}
+
+fn missing(what: &str, file: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("cannot find {} in {}", what, file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const LIB: &str = "pub mod common;
+
+pub mod runner;
+
+pub mod y2018;
+";
+
+    const RUNNER: &str = "use crate::y2018;
+
+const YEARS: &[(u32, &[Solution])] = &[
+    (2018, y2018::SOLUTIONS),
+];
+";
+
+    const YEAR: &str = "//! The 2018 puzzles.
+
+pub mod day5;
+// mod day6;
+// mod day7;
+pub mod day8;
+
+use crate::params::Overrides;
+use crate::runner::{Solution, SolveError};
+
+pub const SOLUTIONS: &[Solution] = &[
+    Solution { day: 5, solve: solve_day5 },
+    Solution { day: 8, solve: solve_day8 },
+];
+";
+
+    /// A crate with just the files the scaffold edits, in a scratch
+    /// directory.
+    fn scratch_crate() -> PathBuf {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let root = std::env::temp_dir().join(format!(
+            "aoc-scaffold-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/y2018")).unwrap();
+
+        for (file, text) in [("src/lib.rs", LIB), ("src/runner.rs", RUNNER), ("src/y2018/mod.rs", YEAR)] {
+            fs::write(root.join(file), text).unwrap();
+        }
+        root
+    }
+
+    fn read(root: &Path, file: &str) -> String {
+        fs::read_to_string(root.join(file)).unwrap()
+    }
+
+    /// The positions of `needles` in `text`, which must be in order.
+    fn assert_in_order(text: &str, needles: &[&str]) {
+        let positions: Vec<usize> = needles
+            .iter()
+            .map(|n| text.find(n).unwrap_or_else(|| panic!("Missing {:?} in:\n{}", n, text)))
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "Out of order: {:?}", needles);
+    }
+
+    #[test]
+    fn it_adds_a_day_to_an_existing_year() {
+        let root = scratch_crate();
+
+        let created = new_day(&root, 2018, 6).unwrap();
+
+        assert_eq!(created, vec![root.join("src/y2018/day6/mod.rs"), root.join("src/y2018/day6/input")]);
+        assert_eq!(read(&root, "src/y2018/day6/input"), "");
+        assert!(read(&root, "src/y2018/day6/mod.rs").contains("map_lines_to_strings(\"./src/y2018/day6/input\")"));
+
+        let year = read(&root, "src/y2018/mod.rs");
+        assert!(!year.contains("// mod day6;"));
+        assert_in_order(&year, &["pub mod day5;", "pub mod day6;", "// mod day7;", "pub mod day8;"]);
+        assert_in_order(&year, &["solve: solve_day5 },\n    Solution { day: 6, solve: solve_day6 },\n    Solution { day: 8"]);
+        assert!(year.ends_with("        day6::solve2(lines)\n    };\n    Ok(answer.to_string())\n}\n"));
+        assert_eq!(read(&root, "src/lib.rs"), LIB);
+        assert_eq!(read(&root, "src/runner.rs"), RUNNER);
+
+        assert_eq!(new_day(&root, 2018, 6).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(new_day(&root, 2018, 26).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn it_adds_a_new_year() {
+        let root = scratch_crate();
+
+        new_day(&root, 2019, 3).unwrap();
+        new_day(&root, 2019, 1).unwrap();
+        new_day(&root, 2017, 1).unwrap();
+
+        assert_in_order(&read(&root, "src/lib.rs"), &["pub mod y2017;", "pub mod y2018;", "pub mod y2019;"]);
+        assert_in_order(
+            &read(&root, "src/runner.rs"),
+            &["use crate::y2017;", "use crate::y2018;", "use crate::y2019;", "(2017, y2017::SOLUTIONS),", "(2018, y2018::SOLUTIONS),", "(2019, y2019::SOLUTIONS),"],
+        );
+        assert_in_order(
+            &read(&root, "src/y2019/mod.rs"),
+            &["//! The 2019 puzzles", "pub mod day1;\npub mod day3;\n\nuse crate::params", "    Solution { day: 1, solve: solve_day1 },\n    Solution { day: 3, solve: solve_day3 },\n];", "fn solve_day3(", "fn solve_day1("],
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+//! Snapshot tests: compare a solver's full output with a reviewed copy
+//! kept next to the day's code.
+//!
+//! When the output changes, or there is no snapshot yet, the new output
+//! is written to `<snapshot>.new` and the test fails. Review the
+//! difference and rename the file to accept it, or run the tests with
+//! `UPDATE_SNAPSHOTS=1` to accept every change at once.
+
+use std::env;
+use std::fmt::{self, Debug};
+use std::fs;
+use std::path::Path;
+
+/// Named values to snapshot together, printed like a struct `Output`
+/// with those fields.
+#[derive(Default)]
+pub struct Output<'a> {
+    fields: Vec<(&'static str, Box<dyn Debug + 'a>)>,
+}
+
+impl<'a> Output<'a> {
+    pub fn new() -> Self {
+        Output::default()
+    }
+
+    pub fn field<T: Debug + 'a>(mut self, name: &'static str, value: T) -> Self {
+        self.fields.push((name, Box::new(value)));
+        self
+    }
+}
+
+impl Debug for Output<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut output = f.debug_struct("Output");
+        for (name, value) in &self.fields {
+            output.field(name, value);
+        }
+        output.finish()
+    }
+}
+
+/// Checks the pretty-printed `value` against the snapshot at `path`.
+pub fn assert_snapshot<T: Debug>(path: &str, value: &T) {
+    let actual = format!("{:#?}\n", value);
+    let pending = format!("{}.new", path);
+
+    if env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1") {
+        write(path, &actual);
+        let _ = fs::remove_file(&pending);
+        return;
+    }
+
+    match fs::read_to_string(path) {
+        Ok(expected) if expected == actual => {
+            let _ = fs::remove_file(&pending);
+        }
+        Ok(expected) => {
+            write(&pending, &actual);
+            panic!(
+                "Snapshot {} changed (new output in {}):\n{}",
+                path,
+                pending,
+                diff(&expected, &actual)
+            );
+        }
+        Err(_) => {
+            write(&pending, &actual);
+            panic!("No snapshot {} yet, review {}:\n{}", path, pending, actual);
+        }
+    }
+}
+
+fn write(path: &str, contents: &str) {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir).expect("Failed to create snapshot directory");
+    }
+    fs::write(path, contents).expect("Failed to write snapshot");
+}
+
+/// The lines that differ, marked `-` for the snapshot and `+` for the new
+/// output. Lines are compared by position, which is enough for the small
+/// structured values snapshotted here.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut out = String::new();
+
+    for i in 0..expected.len().max(actual.len()) {
+        let (old, new) = (expected.get(i), actual.get(i));
+        if old == new {
+            continue;
+        }
+        if let Some(old) = old {
+            out += &format!("{:>4} - {}\n", i + 1, old);
+        }
+        if let Some(new) = new {
+            out += &format!("{:>4} + {}\n", i + 1, new);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_prints_fields_like_a_struct() {
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        struct Output {
+            answer: u32,
+            name: Option<&'static str>,
+        }
+
+        let fields = super::Output::new().field("answer", 42).field("name", Some("day"));
+        let value = Output { answer: 42, name: Some("day") };
+
+        assert_eq!(format!("{:#?}", fields), format!("{:#?}", value));
+        assert_eq!(format!("{:?}", fields), format!("{:?}", value))
+    }
+
+    #[test]
+    fn it_marks_changed_lines() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nx\nc\nd\n"),
+            "   2 - b\n   2 + x\n   4 + d\n"
+        )
+    }
+}
@@ -0,0 +1,725 @@
+//! Submitting answers and keeping track of the verdicts.
+//!
+//! Answers are posted as a form to `<endpoint>/<year>/day/<day>/answer`,
+//! the same way the puzzle site's answer form does. Every verdict about an
+//! answer is appended to the day's attempts file, so an answer that was
+//! already rejected, or that earlier verdicts rule out, is never sent.
+//!
+//! Plain HTTP endpoints are spoken to directly. HTTPS, which the puzzle
+//! site needs, goes through a `curl` binary that must be on the `PATH`.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::runner;
+
+pub const DEFAULT_ENDPOINT: &str = "https://adventofcode.com";
+
+/// The attempts of `profile`, which are kept apart like its inputs. They
+/// are local state rather than source, so they live in the ignored
+/// `attempts` directory instead of next to the day's code.
+pub fn attempts_path(year: u32, day: u32, profile: &str) -> String {
+    runner::with_profile(format!("./attempts/y{}/day{}", year, day), profile)
+}
+
+/// Where answers are sent, and the session cookie to send them with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    url: String,
+    session: Option<String>,
+}
+
+impl Endpoint {
+    pub fn new(url: &str) -> Self {
+        Endpoint {
+            url: url.trim_end_matches('/').to_string(),
+            session: None,
+        }
+    }
+
+    /// The endpoint in `AOC_ENDPOINT`, or the puzzle site, with the
+    /// session in `AOC_SESSION`.
+    pub fn from_env() -> Self {
+        let url = std::env::var("AOC_ENDPOINT").unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string());
+        let endpoint = Endpoint::new(&url);
+        match std::env::var("AOC_SESSION") {
+            Ok(session) => endpoint.session(&session),
+            Err(_) => endpoint,
+        }
+    }
+
+    pub fn session(mut self, session: &str) -> Self {
+        self.session = Some(session.trim().to_string());
+        self
+    }
+
+    fn answer_url(&self, year: u32, day: u32) -> String {
+        format!("{}/{}/day/{}/answer", self.url, year, day)
+    }
+}
+
+/// What the site said about an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// Nothing was checked; try again after the wait.
+    RateLimited { wait: Duration },
+    /// The part is already solved, so nothing was checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Whether the verdict is about the answer, and so worth recording.
+    pub fn is_final(&self) -> bool {
+        !matches!(self, Verdict::RateLimited { .. } | Verdict::AlreadySolved)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited { wait } => write!(f, "rate-limited for {}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already-solved"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    /// Parses the verdicts that are recorded in attempts files.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            _ => Err(format!("unknown verdict {:?}", s)),
+        }
+    }
+}
+
+/// Reads the verdict from the page the site returns, or `None` if the
+/// page is not one it recognises.
+pub fn parse_response(page: &str) -> Option<Verdict> {
+    if page.contains("That's the right answer") {
+        Some(Verdict::Correct)
+    } else if page.contains("answer is too high") {
+        Some(Verdict::TooHigh)
+    } else if page.contains("answer is too low") {
+        Some(Verdict::TooLow)
+    } else if page.contains("That's not the right answer") {
+        Some(Verdict::Wrong)
+    } else if page.contains("You gave an answer too recently") {
+        Some(Verdict::RateLimited {
+            wait: parse_wait(page).unwrap_or_default(),
+        })
+    } else if page.contains("Did you already complete it") {
+        Some(Verdict::AlreadySolved)
+    } else {
+        None
+    }
+}
+
+/// The wait in a sentence such as `You have 1m 5s left to wait.`
+fn parse_wait(page: &str) -> Option<Duration> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+
+    page[start..end]
+        .split_whitespace()
+        .try_fold(0, |total, token| {
+            let (number, unit) = token.split_at(token.len().checked_sub(1)?);
+            let seconds = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            Some(total + number.parse::<u64>().ok()? * seconds)
+        })
+        .map(Duration::from_secs)
+}
+
+/// One answer given for a part, and its verdict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// The attempts made on a day so far, kept in a file with one
+/// `<part> <answer> <verdict>` line per attempt.
+#[derive(Debug)]
+pub struct Attempts {
+    path: String,
+    attempts: Vec<Attempt>,
+}
+
+impl Attempts {
+    /// Loads the attempts in `path`. A missing file has none.
+    pub fn load(path: &str) -> Result<Self, SubmitError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let attempts = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                parse_attempt(line).ok_or_else(|| SubmitError::Corrupt {
+                    path: path.to_string(),
+                    line: index + 1,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Attempts {
+            path: path.to_string(),
+            attempts,
+        })
+    }
+
+    pub fn all(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// The recorded verdict on `answer` for `part`, if it was given before.
+    pub fn verdict(&self, part: u32, answer: &str) -> Option<Verdict> {
+        self.attempts
+            .iter()
+            .find(|a| a.part == part && a.answer == answer)
+            .map(|a| a.verdict)
+    }
+
+    /// The range numeric answers to `part` must lie in, going by the
+    /// attempts judged too low or too high.
+    pub fn bounds(&self, part: u32) -> Bounds {
+        let numbers = |verdict| {
+            self.attempts
+                .iter()
+                .filter(move |a| a.part == part && a.verdict == verdict)
+                .filter_map(|a| a.answer.parse::<i64>().ok())
+        };
+
+        Bounds {
+            above: numbers(Verdict::TooLow).max(),
+            below: numbers(Verdict::TooHigh).min(),
+        }
+    }
+
+    /// What the attempts so far say about giving `answer` to `part`.
+    pub fn assess(&self, part: u32, answer: &str) -> Assessment {
+        if let Some(verdict) = self.verdict(part, answer) {
+            return Assessment::Known(verdict);
+        }
+        if let Some(correct) = self
+            .attempts
+            .iter()
+            .find(|a| a.part == part && a.verdict == Verdict::Correct)
+        {
+            return Assessment::AnotherIsCorrect(correct.answer.clone());
+        }
+
+        let bounds = self.bounds(part);
+        match answer.parse::<i64>() {
+            Ok(n) if bounds.above.is_some_and(|low| n <= low) => Assessment::TooLow(bounds.above.unwrap()),
+            Ok(n) if bounds.below.is_some_and(|high| n >= high) => Assessment::TooHigh(bounds.below.unwrap()),
+            _ => Assessment::Open(bounds),
+        }
+    }
+
+    /// Appends an attempt to the file, creating it and its directory if
+    /// needed.
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), SubmitError> {
+        if let Some(dir) = Path::new(&self.path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{} {} {}", attempt.part, attempt.answer, attempt.verdict)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+/// Exclusive bounds on a numeric answer. Either may be unknown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bounds {
+    /// The highest value judged too low.
+    pub above: Option<i64>,
+    /// The lowest value judged too high.
+    pub below: Option<i64>,
+}
+
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.above, self.below) {
+            (Some(low), Some(high)) => write!(f, "between {} and {}", low, high),
+            (Some(low), None) => write!(f, "above {}", low),
+            (None, Some(high)) => write!(f, "below {}", high),
+            (None, None) => write!(f, "unbounded"),
+        }
+    }
+}
+
+/// What the recorded attempts say about an answer before it is sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Assessment {
+    /// The answer was given before.
+    Known(Verdict),
+    /// A different answer was judged correct.
+    AnotherIsCorrect(String),
+    /// The answer is at most this value, which was too low.
+    TooLow(i64),
+    /// The answer is at least this value, which was too high.
+    TooHigh(i64),
+    /// Nothing rules the answer out yet.
+    Open(Bounds),
+}
+
+impl fmt::Display for Assessment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Assessment::Known(Verdict::Correct) => write!(f, "correct"),
+            Assessment::Known(verdict) => write!(f, "already submitted, {}", verdict),
+            Assessment::AnotherIsCorrect(answer) => write!(f, "wrong, {} is correct", answer),
+            Assessment::TooLow(low) => write!(f, "too low, {} already was", low),
+            Assessment::TooHigh(high) => write!(f, "too high, {} already was", high),
+            Assessment::Open(bounds) if *bounds == Bounds::default() => write!(f, "not submitted"),
+            Assessment::Open(bounds) => write!(f, "not submitted, answer is {}", bounds),
+        }
+    }
+}
+
+fn parse_attempt(line: &str) -> Option<Attempt> {
+    let mut fields = line.split_whitespace();
+    let attempt = Attempt {
+        part: fields.next()?.parse().ok()?,
+        answer: fields.next()?.to_string(),
+        verdict: fields.next()?.parse().ok()?,
+    };
+    match fields.next() {
+        None => Some(attempt),
+        Some(_) => None,
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Io(io::Error),
+    /// The answer was already given and rejected.
+    AlreadyRejected { answer: String, verdict: Verdict },
+    /// Earlier verdicts on other answers show the answer is wrong.
+    RuledOut { answer: String, assessment: Assessment },
+    /// The answer is empty or contains whitespace.
+    InvalidAnswer(String),
+    /// The site answered with an error status or a page without a verdict.
+    UnexpectedResponse { status: u16, body: String },
+    Corrupt { path: String, line: usize },
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitError::Io(e) => write!(f, "{}", e),
+            SubmitError::AlreadyRejected { answer, verdict } => {
+                write!(f, "{} was already submitted and was {}", answer, verdict)
+            }
+            SubmitError::RuledOut { answer, assessment } => {
+                write!(f, "{} is ruled out: {}", answer, assessment)
+            }
+            SubmitError::InvalidAnswer(answer) => write!(f, "invalid answer {:?}", answer),
+            SubmitError::UnexpectedResponse { status, body } => {
+                let excerpt: String = body.chars().take(200).collect();
+                write!(f, "unexpected response (status {}): {}", status, excerpt)
+            }
+            SubmitError::Corrupt { path, line } => write!(f, "{}, line {}: not an attempt", path, line),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<io::Error> for SubmitError {
+    fn from(error: io::Error) -> Self {
+        SubmitError::Io(error)
+    }
+}
+
+/// Submits `answer` for a part unless it has been given before or earlier
+/// verdicts rule it out, and records the verdict. A previously correct
+/// answer is reported correct again without asking the site.
+pub fn submit(
+    endpoint: &Endpoint,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+    attempts: &mut Attempts,
+) -> Result<Verdict, SubmitError> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(SubmitError::InvalidAnswer(answer.to_string()));
+    }
+
+    match attempts.assess(part, answer) {
+        Assessment::Known(Verdict::Correct) => return Ok(Verdict::Correct),
+        Assessment::Known(verdict) => {
+            return Err(SubmitError::AlreadyRejected {
+                answer: answer.to_string(),
+                verdict,
+            })
+        }
+        Assessment::Open(_) => {}
+        assessment => {
+            return Err(SubmitError::RuledOut {
+                answer: answer.to_string(),
+                assessment,
+            })
+        }
+    }
+
+    let form = format!("level={}&answer={}", part, form_encode(answer));
+    let (status, body) = post_form(&endpoint.answer_url(year, day), endpoint.session.as_deref(), &form)?;
+
+    let verdict = match parse_response(&body) {
+        Some(verdict) if status == 200 => verdict,
+        _ => return Err(SubmitError::UnexpectedResponse { status, body }),
+    };
+
+    if verdict.is_final() {
+        attempts.record(Attempt {
+            part,
+            answer: answer.to_string(),
+            verdict,
+        })?;
+    }
+
+    Ok(verdict)
+}
+
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Posts a form and returns the status and body of the response. Plain
+/// HTTP is spoken directly; HTTPS goes through the `curl` command, which
+/// must be on the `PATH`.
+fn post_form(url: &str, session: Option<&str>, form: &str) -> io::Result<(u16, String)> {
+    if url.starts_with("https://") {
+        return post_form_with_curl(url, session, form);
+    }
+
+    let invalid = || io::Error::new(io::ErrorKind::InvalidInput, format!("unsupported URL {}", url));
+    let rest = url.strip_prefix("http://").ok_or_else(invalid)?;
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let host = authority.split(':').next().unwrap_or(authority);
+    let address = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{}:80", authority)
+    };
+
+    let mut stream = TcpStream::connect(address)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+
+    let mut request = format!(
+        "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n",
+        if path.is_empty() { "/" } else { path },
+        host,
+        form.len()
+    );
+    if let Some(session) = session {
+        request += &format!("Cookie: session={}\r\n", session);
+    }
+    request += "\r\n";
+    request += form;
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    let malformed = || io::Error::new(io::ErrorKind::InvalidData, "malformed HTTP response");
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(malformed)?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(malformed)?;
+
+    Ok((status, body.to_string()))
+}
+
+/// The session goes to curl as a config file on its standard input, so it
+/// never shows on the command line, where other users could read it.
+fn post_form_with_curl(url: &str, session: Option<&str>, form: &str) -> io::Result<(u16, String)> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--config", "-"])
+        .args(["--data", form, "--write-out", "\n%{http_code}", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => io::Error::new(e.kind(), "HTTPS submissions need curl on the PATH"),
+            _ => e,
+        })?;
+
+    let config = curl_config(session);
+    let written = child.stdin.take().map_or(Ok(()), |mut stdin| stdin.write_all(config.as_bytes()));
+    let output = child.wait_with_output()?;
+    written?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(io::Error::other(error));
+    }
+
+    let output = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = output.rsplit_once('\n').unwrap_or(("", &output));
+    let status = status
+        .trim()
+        .parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "curl gave no status"))?;
+
+    Ok((status, body.to_string()))
+}
+
+/// A curl config that sends the session cookie, if there is one.
+fn curl_config(session: Option<&str>) -> String {
+    match session {
+        Some(session) => {
+            let quoted = session.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("cookie = \"session={}\"\n", quoted)
+        }
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.</p></article>";
+    const RATE_LIMITED: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2018/day/1\">[Return to Day 1]</a></p></article>";
+    const CORRECT: &str = "<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to fixing the time stream.</p></article>";
+
+    /// Serves `pages` to one connection each, and returns the requests
+    /// received once they have all been served.
+    fn mock_server(pages: Vec<&'static str>) -> (Endpoint, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            pages
+                .into_iter()
+                .map(|page| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let request = read_request(&mut stream);
+                    write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n{}", page).unwrap();
+                    request
+                })
+                .collect()
+        });
+
+        (Endpoint::new(&url).session("abc123"), server)
+    }
+
+    /// Reads the head of a request and as much body as it announces.
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+
+        loop {
+            let len = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..len]);
+
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |n| n.parse().unwrap());
+                if body.len() >= length || len == 0 {
+                    return text;
+                }
+            }
+        }
+    }
+
+    fn scratch_file(name: &str) -> String {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "aoc-{}-{}-{}",
+            name,
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = fs::remove_file(&path);
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn it_reads_verdicts_from_the_page() {
+        assert_eq!(parse_response(TOO_HIGH), Some(Verdict::TooHigh));
+        assert_eq!(parse_response(CORRECT), Some(Verdict::Correct));
+        assert_eq!(
+            parse_response(RATE_LIMITED),
+            Some(Verdict::RateLimited { wait: Duration::from_secs(65) })
+        );
+        assert_eq!(parse_response("<p>That's not the right answer.</p>"), Some(Verdict::Wrong));
+        assert_eq!(parse_response("<html>Maintenance</html>"), None)
+    }
+
+    #[test]
+    fn it_submits_and_records_the_verdict() {
+        let (endpoint, server) = mock_server(vec![TOO_HIGH, CORRECT]);
+        let path = scratch_file("attempts");
+        let mut attempts = Attempts::load(&path).unwrap();
+
+        assert_eq!(submit(&endpoint, 2018, 1, 2, "800", &mut attempts).unwrap(), Verdict::TooHigh);
+        assert_eq!(submit(&endpoint, 2018, 1, 2, "790", &mut attempts).unwrap(), Verdict::Correct);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2018/day/1/answer HTTP/1.0\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=800"));
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2 800 too-high\n2 790 correct\n");
+        assert_eq!(Attempts::load(&path).unwrap().all(), attempts.all());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn it_does_not_resubmit_known_answers() {
+        let (endpoint, server) = mock_server(vec![]);
+        let path = scratch_file("attempts");
+        fs::write(&path, "1 470 correct\n2 800 too-high\n").unwrap();
+        let mut attempts = Attempts::load(&path).unwrap();
+
+        match submit(&endpoint, 2018, 1, 2, "800", &mut attempts) {
+            Err(SubmitError::AlreadyRejected { verdict, .. }) => assert_eq!(verdict, Verdict::TooHigh),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert_eq!(submit(&endpoint, 2018, 1, 1, "470", &mut attempts).unwrap(), Verdict::Correct);
+        assert!(server.join().unwrap().is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn it_does_not_record_rate_limits() {
+        let (endpoint, server) = mock_server(vec![RATE_LIMITED, "<html>Maintenance</html>"]);
+        let path = scratch_file("attempts");
+        let mut attempts = Attempts::load(&path).unwrap();
+
+        assert_eq!(
+            submit(&endpoint, 2018, 1, 1, "470", &mut attempts).unwrap(),
+            Verdict::RateLimited { wait: Duration::from_secs(65) }
+        );
+        assert!(matches!(
+            submit(&endpoint, 2018, 1, 1, "470", &mut attempts),
+            Err(SubmitError::UnexpectedResponse { status: 200, .. })
+        ));
+
+        server.join().unwrap();
+        assert!(attempts.all().is_empty());
+        assert!(!std::path::Path::new(&path).exists())
+    }
+
+    #[test]
+    fn it_derives_bounds_from_the_verdicts() {
+        let path = scratch_file("attempts");
+        fs::write(&path, "2 800 too-high\n2 300 too-low\n2 650 too-high\n2 x12 wrong\n2 500 too-low\n1 900 too-low\n").unwrap();
+        let attempts = Attempts::load(&path).unwrap();
+
+        assert_eq!(attempts.bounds(2), Bounds { above: Some(500), below: Some(650) });
+        assert_eq!(attempts.assess(2, "500"), Assessment::Known(Verdict::TooLow));
+        assert_eq!(attempts.assess(2, "499"), Assessment::TooLow(500));
+        assert_eq!(attempts.assess(2, "700"), Assessment::TooHigh(650));
+        assert_eq!(attempts.assess(2, "600").to_string(), "not submitted, answer is between 500 and 650");
+        assert_eq!(attempts.assess(2, "y7"), Assessment::Open(attempts.bounds(2)));
+        assert_eq!(attempts.assess(3, "1").to_string(), "not submitted");
+        assert_eq!(attempts.bounds(1).to_string(), "above 900");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn it_records_attempts_in_a_new_directory() {
+        let dir = scratch_file("attempts-dir");
+        let path = format!("{}/y2018/day1", dir);
+        let mut attempts = Attempts::load(&path).unwrap();
+
+        attempts.record(Attempt { part: 1, answer: "470".to_string(), verdict: Verdict::Correct }).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 470 correct\n");
+        assert_eq!(attempts_path(2018, 1, runner::DEFAULT_PROFILE), "./attempts/y2018/day1");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn it_refuses_answers_that_are_ruled_out() {
+        let (endpoint, server) = mock_server(vec![]);
+        let path = scratch_file("attempts");
+        fs::write(&path, "1 800 too-high\n2 12 correct\n").unwrap();
+        let mut attempts = Attempts::load(&path).unwrap();
+
+        let error = submit(&endpoint, 2018, 1, 1, "801", &mut attempts).unwrap_err();
+        assert_eq!(error.to_string(), "801 is ruled out: too high, 800 already was");
+
+        let error = submit(&endpoint, 2018, 1, 2, "13", &mut attempts).unwrap_err();
+        assert_eq!(error.to_string(), "13 is ruled out: wrong, 12 is correct");
+
+        assert!(server.join().unwrap().is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn it_sends_the_session_to_curl_through_its_input() {
+        assert_eq!(curl_config(None), "");
+        assert_eq!(curl_config(Some("ab\"c\\")), "cookie = \"session=ab\\\"c\\\\\"\n");
+
+        if Command::new("curl").arg("--version").output().is_err() {
+            return;
+        }
+        let (endpoint, server) = mock_server(vec![CORRECT]);
+        let (status, body) = post_form_with_curl(&endpoint.url, endpoint.session.as_deref(), "level=1&answer=470").unwrap();
+
+        assert_eq!((status, body.as_str()), (200, CORRECT));
+        let request = server.join().unwrap().remove(0);
+        assert!(request.contains("Cookie: session=abc123\r\n"), "{}", request);
+        assert!(request.ends_with("level=1&answer=470"))
+    }
+
+    #[test]
+    fn it_rejects_corrupt_attempts_files() {
+        let path = scratch_file("attempts");
+        fs::write(&path, "1 470 correct\n1 471 maybe\n").unwrap();
+
+        assert!(matches!(Attempts::load(&path), Err(SubmitError::Corrupt { line: 2, .. })));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,537 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::iter::Sum;
+
+use crate::params;
+
+/// The puzzle's parameters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    /// The frequency before the first change.
+    pub start: i64,
+}
+
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "start" => self.start = params::parse_value(value)?,
+            _ => return Err(params::unknown()),
+        }
+        Ok(())
+    }
+}
+
+pub fn solve1<T: Copy + Sum>(numbers: &[T]) -> T {
+    numbers.iter().cloned().sum()
+}
+
+/// The frequency after every change, starting from `params.start`, or
+/// `None` if it overflows.
+pub fn solve1_with<T: Copy + Into<i64>>(numbers: &[T], params: &Params) -> Option<i64> {
+    numbers
+        .iter()
+        .try_fold(params.start, |frequency, &n| frequency.checked_add(n.into()))
+}
+
+pub fn solve2<T>(numbers: &[T]) -> T
+where
+    T: Copy + Into<i64> + TryFrom<i64>,
+{
+    let repeat = first_repeat(numbers).expect("No frequency is reached twice");
+    T::try_from(repeat).unwrap_or_else(|_| panic!("Frequency {} is out of range", repeat))
+}
+
+/// Finds the first frequency reached twice when the changes are applied
+/// over and over, or `None` if no frequency ever repeats.
+pub fn first_repeat<T: Copy + Into<i64>>(numbers: &[T]) -> Option<i64> {
+    first_repeat_with(numbers, &Params::default())
+}
+
+/// Like `first_repeat`, starting from `params.start`. The changes must be
+/// `in_range_with` the parameters.
+pub fn first_repeat_with<T: Copy + Into<i64>>(numbers: &[T], params: &Params) -> Option<i64> {
+    let changes: Vec<i64> = numbers.iter().map(|&n| n.into()).collect();
+    find_repeat(&prefixes(&changes, params.start), solve1(&changes)).map(|(_, frequency)| frequency)
+}
+
+/// Whether the changes are small enough for `first_repeat` and `solve1`:
+/// every frequency reached within a pass, and the difference between any
+/// two of them, must fit in an `i64`.
+pub fn in_range(changes: &[i64]) -> bool {
+    in_range_with(changes, &Params::default())
+}
+
+/// Like `in_range`, for frequencies starting from `params.start`.
+pub fn in_range_with(changes: &[i64], params: &Params) -> bool {
+    params
+        .start
+        .checked_abs()
+        .and_then(|start| {
+            changes
+                .iter()
+                .try_fold(start, |total, &change| total.checked_add(change.checked_abs()?))
+        })
+        .is_some_and(|total| total <= i64::MAX / 2)
+}
+
+/// Frequencies before each change of the first pass, from `start`.
+fn prefixes(changes: &[i64], start: i64) -> Vec<i64> {
+    changes
+        .iter()
+        .scan(start, |frequency, &change| {
+            let before = *frequency;
+            *frequency += change;
+            Some(before)
+        })
+        .collect()
+}
+
+/// Returns the step at which the first repeat happens and the repeated
+/// frequency. Step `t` is the frequency after `t` changes.
+///
+/// Every frequency reached is `prefix[i] + k * drift`, where `drift` is the
+/// change per pass. Unless the first pass already repeats itself, a repeat
+/// can only happen between two prefixes that are congruent modulo the
+/// drift, and the earliest one is always between neighbours in such a
+/// group.
+fn find_repeat(prefixes: &[i64], drift: i64) -> Option<(u64, i64)> {
+    let mut seen: HashSet<i64> = HashSet::new();
+    for (index, &prefix) in prefixes.iter().enumerate() {
+        if !seen.insert(prefix) {
+            return Some((index as u64, prefix));
+        }
+    }
+
+    if prefixes.is_empty() {
+        return None;
+    }
+    if drift == 0 {
+        return Some((prefixes.len() as u64, prefixes[0]));
+    }
+
+    let mut groups: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+    for (index, &prefix) in prefixes.iter().enumerate() {
+        groups
+            .entry(prefix.rem_euclid(drift))
+            .or_default()
+            .push((prefix, index));
+    }
+
+    // Candidates are ordered by (passes, index), which is the order in
+    // which they are reached.
+    let mut best: Option<(u64, usize, i64)> = None;
+    for group in groups.values_mut() {
+        group.sort_unstable();
+        if drift < 0 {
+            group.reverse();
+        }
+
+        for pair in group.windows(2) {
+            let ((from, index), (to, _)) = (pair[0], pair[1]);
+            let passes = ((to - from) / drift) as u64;
+            let candidate = (passes, index, to);
+            if best.is_none_or(|b| (candidate.0, candidate.1) < (b.0, b.1)) {
+                best = Some(candidate);
+            }
+        }
+    }
+
+    best.map(|(passes, index, frequency)| {
+        let step = passes.saturating_mul(prefixes.len() as u64);
+        (step.saturating_add(index as u64), frequency)
+    })
+}
+
+/// Where the first repeated frequency was reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    pub frequency: i64,
+    /// Number of complete passes over the changes before the repeat.
+    pub full_passes: u64,
+    /// Index of the change that produced the repeated frequency.
+    pub change_index: usize,
+}
+
+/// The running frequency from the start up to the first repeat, or over
+/// a single pass if no frequency ever repeats.
+#[derive(Debug, Clone)]
+pub struct FrequencyTrace {
+    start: i64,
+    changes: Vec<i64>,
+    prefixes: Vec<i64>,
+    drift: i64,
+    steps: u64,
+    repeat: Option<Repeat>,
+}
+
+impl FrequencyTrace {
+    pub fn new<T: Copy + Into<i64>>(numbers: &[T]) -> Self {
+        FrequencyTrace::new_with(numbers, &Params::default())
+    }
+
+    /// Like `new`, starting from `params.start`. The changes must be
+    /// `in_range_with` the parameters.
+    pub fn new_with<T: Copy + Into<i64>>(numbers: &[T], params: &Params) -> Self {
+        let changes: Vec<i64> = numbers.iter().map(|&n| n.into()).collect();
+        let prefixes = prefixes(&changes, params.start);
+        let drift = solve1(&changes);
+        let n = changes.len() as u64;
+
+        let (steps, repeat) = match find_repeat(&prefixes, drift) {
+            Some((step, frequency)) => (
+                step,
+                Some(Repeat {
+                    frequency,
+                    full_passes: (step - 1) / n,
+                    change_index: ((step - 1) % n) as usize,
+                }),
+            ),
+            None => (n, None),
+        };
+
+        FrequencyTrace {
+            start: params.start,
+            changes,
+            prefixes,
+            drift,
+            steps,
+            repeat,
+        }
+    }
+
+    pub fn first_repeat(&self) -> Option<Repeat> {
+        self.repeat
+    }
+
+    /// Number of changes applied in the trace.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Frequency after `step` changes.
+    pub fn frequency_at(&self, step: u64) -> i64 {
+        let n = self.prefixes.len() as u64;
+        if n == 0 {
+            return self.start;
+        }
+        self.prefixes[(step % n) as usize] + (step / n) as i64 * self.drift
+    }
+
+    /// The running frequency series, from the start to the last step.
+    pub fn frequencies(&self) -> impl Iterator<Item = i64> + '_ {
+        (0..=self.steps).map(move |step| self.frequency_at(step))
+    }
+
+    /// Lowest and highest frequency reached in the trace.
+    pub fn min_max(&self) -> (i64, i64) {
+        let n = self.prefixes.len() as u64;
+        if n == 0 {
+            return (self.start, self.start);
+        }
+
+        // The frequency grows linearly with the pass for a given index, so
+        // the extremes are in the first pass or the last pass reaching it.
+        let (last_pass, last_index) = (self.steps / n, (self.steps % n) as usize);
+        let mut min = i64::MAX;
+        let mut max = i64::MIN;
+
+        for (index, &prefix) in self.prefixes.iter().enumerate() {
+            let passes = if index <= last_index {
+                last_pass
+            } else if last_pass > 0 {
+                last_pass - 1
+            } else {
+                continue;
+            };
+
+            for frequency in [prefix, prefix + passes as i64 * self.drift] {
+                min = min.min(frequency);
+                max = max.max(frequency);
+            }
+        }
+
+        (min, max)
+    }
+
+    /// Writes the series as CSV with one row per step. The first row is
+    /// the starting frequency, so it has no pass or change.
+    pub fn write_csv<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let n = self.changes.len() as u64;
+        writeln!(out, "step,pass,change_index,change,frequency")?;
+        writeln!(out, "0,,,,{}", self.start)?;
+
+        for step in 1..=self.steps {
+            let (pass, index) = ((step - 1) / n, ((step - 1) % n) as usize);
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                step,
+                pass,
+                index,
+                self.changes[index],
+                self.frequency_at(step)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::*;
+    use crate::check::*;
+    use crate::snapshot::{assert_snapshot, Output};
+
+    #[test]
+    fn it_sums_the_array() {
+        let result: i32 = solve1(&[1, 2, 3]);
+        assert_eq!(result, 1 + 2 + 3)
+    }
+
+    #[test]
+    fn it_handles_example_input_1() {
+        let result: i32 = solve1(&[1, 1, 1]);
+        assert_eq!(result, 3)
+    }
+
+    #[test]
+    fn it_handles_example_input_2() {
+        let result: i32 = solve1(&[1, 1, -2]);
+        assert_eq!(result, 0)
+    }
+
+    #[test]
+    fn it_handles_example_input_3() {
+        let result: i32 = solve1(&[-1, -2, -3]);
+        assert_eq!(result, -6)
+    }
+
+    #[test]
+    fn it_handles_example_input_4() {
+        let result: i32 = solve1(&[1, -2, 3, 1]);
+        assert_eq!(result, 3)
+    }
+
+    #[test]
+    fn it_handles_real_input() {
+        let numbers = map_lines_to_int32("./src/y2018/day1/input");
+        let result: i32 = solve1(&numbers);
+        assert_eq!(result, 470);
+    }
+
+    #[test]
+    fn it_handles_example_input_4_star_2() {
+        let result: i32 = solve2(&[1, -2, 3, 1]);
+        assert_eq!(result, 2)
+    }
+
+    #[test]
+    fn it_handles_real_input_star_2() {
+        let numbers = map_lines_to_int32("./src/y2018/day1/input");
+        let result: i32 = solve2(&numbers);
+        assert_eq!(result, 790);
+    }
+
+    #[test]
+    fn it_sums_past_the_range_of_i32() {
+        let result: i64 = solve1(&[i32::MAX as i64, 1, 1]);
+        assert_eq!(result, i32::MAX as i64 + 2)
+    }
+
+    #[test]
+    fn it_handles_real_input_as_i64() {
+        let numbers: Vec<i64> = load_integers("./src/y2018/day1/input").unwrap();
+        assert_eq!(solve1(&numbers), 470);
+        assert_eq!(solve2(&numbers), 790)
+    }
+
+    /// Applies the changes one at a time until a frequency repeats. A
+    /// frequency of the first pass comes back, if ever, within the number
+    /// of passes it takes the drift to cover the range of that pass, so
+    /// giving up after that many is `None` for sure.
+    fn first_repeat_by_simulation(numbers: &[i64]) -> Option<i64> {
+        let mut frequencies = vec![0];
+        frequencies.extend(numbers.iter().scan(0, |sum, &num| {
+            *sum += num;
+            Some(*sum)
+        }));
+        let range = frequencies.iter().max()? - frequencies.iter().min()?;
+        let drift = frequencies.last()?.abs().max(1);
+        let passes = range / drift + 2;
+
+        let mut seen: HashSet<i64> = HashSet::new();
+        seen.insert(0);
+        let mut sum = 0;
+        for num in numbers.iter().cycle().take(numbers.len() * passes as usize) {
+            sum += num;
+            if !seen.insert(sum) {
+                return Some(sum);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn it_handles_the_examples_star_2() {
+        assert_eq!(first_repeat(&[1, -1]), Some(0));
+        assert_eq!(first_repeat(&[3, 3, 4, -2, -4]), Some(10));
+        assert_eq!(first_repeat(&[-6, 3, 8, 5, -6]), Some(5));
+        assert_eq!(first_repeat(&[7, 7, -2, -7, -4]), Some(14))
+    }
+
+    #[test]
+    fn it_detects_frequencies_that_never_repeat() {
+        assert_eq!(first_repeat(&[1, 1]), None);
+        assert_eq!(first_repeat(&[-3, 1]), None);
+        assert_eq!(first_repeat::<i64>(&[]), None)
+    }
+
+    #[test]
+    fn it_agrees_with_simulation_on_small_inputs() {
+        Property::new("./src/y2018/day1/regressions").cases(2000).check(
+            |rng| {
+                let len = 1 + rng.below(6) as usize;
+                let numbers: Vec<String> = (0..len).map(|_| rng.range(-10, 10).to_string()).collect();
+                numbers.join(" ")
+            },
+            shrink_words,
+            |case| {
+                let numbers: Vec<i64> = case.split_whitespace().map(|n| n.parse().unwrap()).collect();
+                first_repeat(&numbers) == first_repeat_by_simulation(&numbers)
+            },
+        );
+    }
+
+    #[test]
+    fn it_handles_a_huge_drift_quickly() {
+        assert_eq!(first_repeat(&[1_000_000_000, -999_999_999]), Some(1_000_000_000))
+    }
+
+    #[test]
+    fn it_traces_the_example() {
+        let trace = FrequencyTrace::new(&[1, -2, 3, 1]);
+
+        assert_eq!(trace.frequencies().collect::<Vec<_>>(), vec![0, 1, -1, 2, 3, 4, 2]);
+        assert_eq!(trace.min_max(), (-1, 4));
+        assert_eq!(
+            trace.first_repeat(),
+            Some(Repeat {
+                frequency: 2,
+                full_passes: 1,
+                change_index: 1,
+            })
+        )
+    }
+
+    #[test]
+    fn it_traces_a_single_pass_when_nothing_repeats() {
+        let trace = FrequencyTrace::new(&[1, 1]);
+
+        assert_eq!(trace.first_repeat(), None);
+        assert_eq!(trace.frequencies().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(trace.min_max(), (0, 2))
+    }
+
+    #[test]
+    fn it_traces_a_repeat_within_the_first_pass() {
+        let trace = FrequencyTrace::new(&[1, -1, 5]);
+
+        assert_eq!(trace.frequencies().collect::<Vec<_>>(), vec![0, 1, 0]);
+        assert_eq!(trace.first_repeat().map(|r| (r.full_passes, r.change_index)), Some((0, 1)))
+    }
+
+    #[test]
+    fn it_agrees_with_the_series_on_min_and_max() {
+        let trace = FrequencyTrace::new(&[7, 7, -2, -7, -4]);
+        let series: Vec<i64> = trace.frequencies().collect();
+
+        assert_eq!(trace.min_max(), (*series.iter().min().unwrap(), *series.iter().max().unwrap()));
+        assert_eq!(series.last(), Some(&14))
+    }
+
+    #[test]
+    fn it_traces_the_real_input() {
+        let numbers: Vec<i64> = load_integers("./src/y2018/day1/input").unwrap();
+        let trace = FrequencyTrace::new(&numbers);
+        let series: Vec<i64> = trace.frequencies().collect();
+
+        let repeat = trace.first_repeat().unwrap();
+        assert_eq!(repeat.frequency, 790);
+        assert_eq!(numbers.len() as u64 * repeat.full_passes + repeat.change_index as u64 + 1, trace.steps());
+        assert_eq!(trace.min_max(), (*series.iter().min().unwrap(), *series.iter().max().unwrap()))
+    }
+
+    #[test]
+    fn it_exports_the_trace_as_csv() {
+        let mut csv = Vec::new();
+        FrequencyTrace::new(&[1, -2, 3, 1]).write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "step,pass,change_index,change,frequency
+0,,,,0
+1,0,0,1,1
+2,0,1,-2,-1
+3,0,2,3,2
+4,0,3,1,3
+5,1,0,1,4
+6,1,1,-2,2
+"
+        )
+    }
+
+    #[test]
+    fn it_checks_changes_are_in_range() {
+        assert!(in_range(&[1, -2, 3, 1]));
+        assert!(!in_range(&[i64::MAX / 2, 1]));
+        assert!(!in_range(&[i64::MIN]))
+    }
+
+    #[test]
+    fn it_starts_from_another_frequency() {
+        use crate::params::Params as _;
+
+        let mut params = Params::default();
+        params.set("start", "100").unwrap();
+        let changes = [1, -2, 3, 1];
+
+        assert_eq!(solve1_with(&changes, &params), Some(103));
+        assert_eq!(first_repeat_with(&changes, &params), Some(102));
+        assert_eq!(first_repeat_with(&[1, -1], &params), Some(100));
+        assert_eq!(first_repeat_with(&[1, 1], &params), None);
+
+        let trace = FrequencyTrace::new_with(&changes, &params);
+        assert_eq!(trace.frequencies().collect::<Vec<_>>(), vec![100, 101, 99, 102, 103, 104, 102]);
+        assert_eq!(trace.min_max(), (99, 104));
+        assert_eq!(trace.first_repeat().map(|repeat| repeat.frequency), Some(102));
+        assert_eq!(FrequencyTrace::new_with::<i64>(&[], &params).min_max(), (100, 100));
+
+        assert!(in_range_with(&changes, &params));
+        params.set("start", &(i64::MAX / 2).to_string()).unwrap();
+        assert!(!in_range_with(&changes, &params));
+        assert_eq!(solve1_with(&[i64::MAX], &params), None)
+    }
+
+    fn output(numbers: &[i64]) -> Output<'static> {
+        let trace = FrequencyTrace::new(numbers);
+        Output::new()
+            .field("frequency", solve1(numbers))
+            .field("first_repeat", trace.first_repeat())
+            .field("steps", trace.steps())
+            .field("min_max", trace.min_max())
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_example() {
+        assert_snapshot("./src/y2018/day1/snapshots/example.snap", &output(&[1, -2, 3, 1]))
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_input() {
+        let numbers: Vec<i64> = load_integers("./src/y2018/day1/input").unwrap();
+        assert_snapshot("./src/y2018/day1/snapshots/input.snap", &output(&numbers))
+    }
+}
@@ -0,0 +1,583 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+pub fn solve1(hashes: Vec<&str>) -> u64 {
+    checksum(&hashes, &[2, 3]).product().expect("The checksum overflows")
+}
+
+pub fn count_letters(id: &str) -> (bool, bool) {
+    let multiplicities = letter_multiplicities(id);
+
+    (multiplicities.contains(&2), multiplicities.contains(&3))
+}
+
+/// The distinct numbers of times any letter occurs in `id`.
+fn letter_multiplicities(id: &str) -> HashSet<u32> {
+    let mut letters_count: HashMap<char, u32> = HashMap::new();
+
+    for letter in id.chars() {
+        *letters_count.entry(letter).or_insert(0) += 1;
+    }
+
+    letters_count.into_values().collect()
+}
+
+/// For each multiplicity, the number of IDs containing some letter exactly
+/// that many times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checksum {
+    pub counts: BTreeMap<u32, u64>,
+}
+
+impl Checksum {
+    pub fn count(&self, multiplicity: u32) -> u64 {
+        self.counts.get(&multiplicity).cloned().unwrap_or(0)
+    }
+
+    /// The product of the counts, or `None` if it overflows.
+    pub fn product(&self) -> Option<u64> {
+        self.counts.values().try_fold(1u64, |product, &count| product.checked_mul(count))
+    }
+}
+
+pub fn checksum(ids: &[&str], multiplicities: &[u32]) -> Checksum {
+    let mut counts: BTreeMap<u32, u64> = multiplicities.iter().map(|&m| (m, 0)).collect();
+
+    for id in ids {
+        let found = letter_multiplicities(id);
+        for (multiplicity, count) in counts.iter_mut() {
+            if found.contains(multiplicity) {
+                *count += 1;
+            }
+        }
+    }
+
+    Checksum { counts }
+}
+
+pub fn solve2(hashes: Vec<&str>) -> String {
+    find_common_chars(&hashes).expect("No words of distance 1 found")
+}
+
+/// Letters shared by the first pair of IDs that differ in exactly one
+/// position, if there is such a pair.
+pub fn find_common_chars(hashes: &[&str]) -> Option<String> {
+    IdIndex::new(hashes)
+        .pairs_at_distance(1)
+        .first()
+        .and_then(|(h1, h2)| common_chars(h1, h2).ok())
+}
+
+/// Index over box IDs for finding pairs that differ in a given number of
+/// positions without comparing every pair. Only IDs of equal length are
+/// ever paired.
+pub struct IdIndex<'a> {
+    ids: Vec<&'a str>,
+    chars: Vec<Vec<char>>,
+}
+
+impl<'a> IdIndex<'a> {
+    pub fn new(ids: &[&'a str]) -> Self {
+        IdIndex {
+            ids: ids.to_vec(),
+            chars: ids.iter().map(|id| id.chars().collect()).collect(),
+        }
+    }
+
+    /// All pairs of IDs that differ in exactly `k` positions, in the order
+    /// the IDs were given.
+    pub fn pairs_at_distance(&self, k: usize) -> Vec<(&'a str, &'a str)> {
+        self.index_pairs_at_distance(k)
+            .into_iter()
+            .map(|(i, j)| (self.ids[i], self.ids[j]))
+            .collect()
+    }
+
+    /// Like `pairs_at_distance`, but giving the positions of the IDs in
+    /// the original list, with the lower position first.
+    pub fn index_pairs_at_distance(&self, k: usize) -> Vec<(usize, usize)> {
+        let mut pairs: Vec<(usize, usize)> = if k == 1 {
+            self.candidates_by_deletion()
+        } else {
+            self.candidates_by_blocks(k)
+        }
+        .into_iter()
+        .filter(|&(i, j)| {
+            let (chars1, chars2) = (&self.chars[i], &self.chars[j]);
+            chars1.len() == chars2.len() && hamming(chars1, chars2) == k
+        })
+        .collect();
+
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// IDs at distance 1 become equal once the differing position is
+    /// deleted, so bucketing every ID by each of its deletions brings
+    /// them together.
+    fn candidates_by_deletion(&self) -> HashSet<(usize, usize)> {
+        let mut buckets: HashMap<(usize, String), Vec<usize>> = HashMap::new();
+
+        for (id, chars) in self.chars.iter().enumerate() {
+            for position in 0..chars.len() {
+                let deleted: String = chars
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != position)
+                    .map(|(_, c)| c)
+                    .collect();
+                buckets.entry((position, deleted)).or_default().push(id);
+            }
+        }
+
+        pairs_in_buckets(buckets.values())
+    }
+
+    /// Two IDs within distance `k` agree on at least one of `k + 1`
+    /// blocks, so bucketing by every block finds all candidates.
+    fn candidates_by_blocks(&self, k: usize) -> HashSet<(usize, usize)> {
+        let mut buckets: HashMap<(usize, usize, String), Vec<usize>> = HashMap::new();
+
+        for (id, chars) in self.chars.iter().enumerate() {
+            let len = chars.len();
+            for block in 0..=k {
+                let (from, to) = (block * len / (k + 1), (block + 1) * len / (k + 1));
+                let content: String = chars[from..to].iter().collect();
+                buckets.entry((len, block, content)).or_default().push(id);
+            }
+        }
+
+        pairs_in_buckets(buckets.values())
+    }
+}
+
+fn pairs_in_buckets<'b, I>(buckets: I) -> HashSet<(usize, usize)>
+where
+    I: Iterator<Item = &'b Vec<usize>>,
+{
+    let mut pairs = HashSet::new();
+
+    for bucket in buckets {
+        for (n, &i) in bucket.iter().enumerate() {
+            for &j in bucket[n + 1..].iter() {
+                pairs.insert((i.min(j), i.max(j)));
+            }
+        }
+    }
+
+    pairs
+}
+
+/// The number of positions that differ between two equally long slices.
+fn hamming<T: PartialEq>(items1: &[T], items2: &[T]) -> usize {
+    items1.iter().zip(items2).filter(|(i1, i2)| i1 != i2).count()
+}
+
+/// The number of chars that differ between two equally long IDs.
+pub fn distance(str1: &str, str2: &str) -> Result<u32, DistanceError> {
+    string_distance(str1, str2, Metric::Hamming, Segmentation::Chars).map(|d| d as u32)
+}
+
+/// The chars two equally long IDs have in common.
+fn common_chars(str1: &str, str2: &str) -> Result<String, DistanceError> {
+    checked_common_chars(str1, str2, Segmentation::Chars)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Number of positions that differ. Both strings must be equally long.
+    Hamming,
+    /// Number of insertions, deletions and substitutions needed.
+    Levenshtein,
+}
+
+/// What counts as one position when comparing strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segmentation {
+    Chars,
+    /// Extended grapheme clusters, so that e.g. `e` followed by a
+    /// combining accent is a single position.
+    Graphemes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DistanceError {
+    LengthMismatch { left: usize, right: usize },
+}
+
+impl fmt::Display for DistanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DistanceError::LengthMismatch { left, right } => {
+                write!(f, "strings differ in length ({} and {})", left, right)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DistanceError {}
+
+fn segments(string: &str, segmentation: Segmentation) -> Vec<&str> {
+    match segmentation {
+        Segmentation::Chars => string
+            .char_indices()
+            .map(|(i, c)| &string[i..i + c.len_utf8()])
+            .collect(),
+        Segmentation::Graphemes => string.graphemes(true).collect(),
+    }
+}
+
+fn equal_length_segments<'a, 'b>(
+    str1: &'a str,
+    str2: &'b str,
+    segmentation: Segmentation,
+) -> Result<(Vec<&'a str>, Vec<&'b str>), DistanceError> {
+    let (segments1, segments2) = (segments(str1, segmentation), segments(str2, segmentation));
+
+    if segments1.len() != segments2.len() {
+        return Err(DistanceError::LengthMismatch {
+            left: segments1.len(),
+            right: segments2.len(),
+        });
+    }
+
+    Ok((segments1, segments2))
+}
+
+pub fn string_distance(
+    str1: &str,
+    str2: &str,
+    metric: Metric,
+    segmentation: Segmentation,
+) -> Result<usize, DistanceError> {
+    match metric {
+        Metric::Hamming => {
+            let (segments1, segments2) = equal_length_segments(str1, str2, segmentation)?;
+            Ok(hamming(&segments1, &segments2))
+        }
+        Metric::Levenshtein => Ok(levenshtein(
+            &segments(str1, segmentation),
+            &segments(str2, segmentation),
+        )),
+    }
+}
+
+fn levenshtein(segments1: &[&str], segments2: &[&str]) -> usize {
+    let mut previous: Vec<usize> = (0..=segments2.len()).collect();
+
+    for (i, s1) in segments1.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, s2) in segments2.iter().enumerate() {
+            let substitution = previous[j] + if s1 == s2 { 0 } else { 1 };
+            let deletion = previous[j + 1] + 1;
+            let insertion = current[j] + 1;
+            current.push(substitution.min(deletion).min(insertion));
+        }
+        previous = current;
+    }
+
+    previous[segments2.len()]
+}
+
+/// The positions two equally long strings have in common.
+pub fn checked_common_chars(
+    str1: &str,
+    str2: &str,
+    segmentation: Segmentation,
+) -> Result<String, DistanceError> {
+    let (segments1, segments2) = equal_length_segments(str1, str2, segmentation)?;
+
+    Ok(segments1
+        .iter()
+        .zip(segments2.iter())
+        .filter_map(|(s1, s2)| if s1 == s2 { Some(*s1) } else { None })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::*;
+    use crate::check::*;
+    use crate::snapshot::{assert_snapshot, Output};
+
+    #[test]
+    fn it_returns_a_boolean_tuple() {
+        let _result: (bool, bool) = count_letters("abcdef");
+    }
+
+    #[test]
+    fn it_returns_true_if_string_contains_2_of_a_letter() {
+        let (result, _) = count_letters("abbcde");
+        assert!(result)
+    }
+
+    #[test]
+    fn it_returns_false_if_string_doesnt_contain_2_of_a_letter() {
+        let (result, _) = count_letters("abcdef");
+        assert!(!result)
+    }
+
+    #[test]
+    fn it_returns_true_if_string_contains_3_of_a_letter() {
+        let (_, result) = count_letters("abcccd");
+        assert!(result)
+    }
+
+    #[test]
+    fn it_returns_false_if_string_doesnt_contain_3_of_a_letter() {
+        let (_, result) = count_letters("abcdef");
+        assert!(!result)
+    }
+
+    #[test]
+    fn it_returns_true_if_string_contains_both_2_and_3_of_a_letter() {
+        let (contains_2, contains_3) = count_letters("bababc");
+        assert!(contains_2 && contains_3)
+    }
+
+    #[test]
+    fn it_returns_the_product_of_no_of_2s_and_no_of_3s() {
+        let strings = vec![
+            "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
+        ];
+
+        let product = solve1(strings);
+
+        assert_eq!(product, 12)
+    }
+
+    #[test]
+    fn it_returns_the_product_of_no_of_2s_and_no_of_3s_for_test_input() {
+        let strings = map_lines_to_strings("./src/y2018/day2/input");
+        let strings = strings.iter().map(|s| s.as_str()).collect();
+
+        let product = solve1(strings);
+
+        assert_eq!(product, 5434)
+    }
+
+    #[test]
+    fn it_counts_distance_strings_that_differ_by_2() {
+        let result = distance("abcde", "axcye");
+        assert_eq!(result, Ok(2))
+    }
+
+    #[test]
+    fn it_counts_distance_strings_that_differ_by_1() {
+        let result = distance("fghij", "fguij");
+        assert_eq!(result, Ok(1))
+    }
+
+    #[test]
+    fn it_prints_the_common_chars() {
+        let result = common_chars("fghij", "fguij");
+        assert_eq!(result, Ok("fgij".to_string()))
+    }
+
+    #[test]
+    fn it_finds_the_two_strings_that_have_distance_1_for_example_input() {
+        let strs = vec![
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ];
+
+        let result = solve2(strs);
+        assert_eq!(result, "fgij")
+    }
+
+    #[test]
+    fn it_finds_the_two_strings_that_have_distance_1_for_real_input() {
+        let strings = map_lines_to_strings("./src/y2018/day2/input");
+        let strings = strings.iter().map(|s| s.as_str()).collect();
+
+        let result = solve2(strings);
+        assert_eq!(result, "agimdjvlhedpsyoqfzuknpjwt")
+    }
+
+    #[test]
+    fn it_returns_none_when_no_ids_are_at_distance_1() {
+        assert_eq!(find_common_chars(&["abcde", "axcye", "fghij"]), None)
+    }
+
+    #[test]
+    fn it_finds_all_pairs_at_distance_1() {
+        let index = IdIndex::new(&["abcd", "abce", "xbcd", "abcd", "abc"]);
+
+        assert_eq!(
+            index.pairs_at_distance(1),
+            vec![
+                ("abcd", "abce"),
+                ("abcd", "xbcd"),
+                ("abce", "abcd"),
+                ("xbcd", "abcd"),
+            ]
+        )
+    }
+
+    #[test]
+    fn it_finds_pairs_at_distance_k() {
+        let ids = ["abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz"];
+        let index = IdIndex::new(&ids);
+
+        assert_eq!(index.pairs_at_distance(0), vec![]);
+        assert_eq!(index.pairs_at_distance(2), vec![("abcde", "axcye")]);
+        assert_eq!(index.pairs_at_distance(5).len(), 18)
+    }
+
+    #[test]
+    fn it_matches_pairwise_comparison_for_real_input() {
+        let strings = map_lines_to_strings("./src/y2018/day2/input");
+        let ids: Vec<&str> = strings.iter().map(|s| s.as_str()).collect();
+        let index = IdIndex::new(&ids);
+
+        for k in 0..4 {
+            let mut expected = vec![];
+            for (i, id1) in ids.iter().enumerate() {
+                for id2 in ids[i + 1..].iter() {
+                    if distance(id1, id2) == Ok(k as u32) {
+                        expected.push((*id1, *id2));
+                    }
+                }
+            }
+            let mut found = index.pairs_at_distance(k);
+            found.sort();
+            expected.sort();
+
+            assert_eq!(found, expected, "distance {}", k);
+        }
+    }
+
+    #[test]
+    fn it_reports_a_length_mismatch() {
+        let result = string_distance("abc", "abcd", Metric::Hamming, Segmentation::Chars);
+        assert_eq!(result, Err(DistanceError::LengthMismatch { left: 3, right: 4 }));
+
+        let result = checked_common_chars("abcd", "abc", Segmentation::Chars);
+        assert_eq!(result, Err(DistanceError::LengthMismatch { left: 4, right: 3 }));
+
+        assert_eq!(distance("fghij", "fgu"), Err(DistanceError::LengthMismatch { left: 5, right: 3 }));
+        assert_eq!(common_chars("fgu", "fghij"), Err(DistanceError::LengthMismatch { left: 3, right: 5 }))
+    }
+
+    #[test]
+    fn it_computes_the_levenshtein_distance() {
+        let distance = |s1, s2| string_distance(s1, s2, Metric::Levenshtein, Segmentation::Chars);
+
+        assert_eq!(distance("kitten", "sitting"), Ok(3));
+        assert_eq!(distance("abc", "abcd"), Ok(1));
+        assert_eq!(distance("", "abc"), Ok(3));
+        assert_eq!(distance("fghij", "fguij"), Ok(1))
+    }
+
+    #[test]
+    fn it_compares_grapheme_clusters() {
+        // "e\u{301}" is one grapheme made of two chars
+        let (accented, plain) = ("cafe\u{301}s", "cafes");
+
+        assert_eq!(
+            string_distance(accented, plain, Metric::Hamming, Segmentation::Graphemes),
+            Ok(1)
+        );
+        assert_eq!(
+            string_distance(accented, plain, Metric::Hamming, Segmentation::Chars),
+            Err(DistanceError::LengthMismatch { left: 6, right: 5 })
+        );
+        assert_eq!(
+            string_distance(accented, plain, Metric::Levenshtein, Segmentation::Chars),
+            Ok(1)
+        );
+        assert_eq!(
+            checked_common_chars(accented, "cafe\u{301}x", Segmentation::Graphemes),
+            Ok("cafe\u{301}".to_string())
+        )
+    }
+
+    #[test]
+    fn it_counts_each_requested_multiplicity() {
+        let strings = vec![
+            "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab", "aaaabb",
+        ];
+
+        let result = checksum(&strings, &[2, 3, 4]);
+
+        assert_eq!((result.count(2), result.count(3), result.count(4)), (5, 3, 1));
+        assert_eq!(result.product(), Some(15));
+        assert_eq!(result.count(5), 0)
+    }
+
+    #[test]
+    fn it_has_an_empty_product_without_multiplicities() {
+        assert_eq!(checksum(&["aa"], &[]).product(), Some(1))
+    }
+
+    #[test]
+    fn it_has_no_product_when_it_overflows() {
+        let checksum = Checksum {
+            counts: [(2, u64::MAX / 2), (3, 2)].into_iter().collect(),
+        };
+        assert_eq!(checksum.product(), Some(u64::MAX - 1));
+
+        let checksum = Checksum {
+            counts: [(2, u64::MAX / 2), (3, 3)].into_iter().collect(),
+        };
+        assert_eq!(checksum.product(), None)
+    }
+
+    #[test]
+    fn it_indexes_like_pairwise_comparison() {
+        Property::new("./src/y2018/day2/regressions").check(
+            |rng| {
+                let (size, length) = (rng.below(12) as usize, 1 + rng.below(5) as usize);
+                let ids: Vec<String> = (0..size)
+                    .map(|_| (0..length).map(|_| rng.letter(3)).collect())
+                    .collect();
+                ids.join("\n")
+            },
+            shrink_lines,
+            |case| {
+                let ids: Vec<&str> = case.lines().collect();
+                let index = IdIndex::new(&ids);
+
+                (0..4).all(|k| {
+                    let mut expected = vec![];
+                    for (i, id1) in ids.iter().enumerate() {
+                        for (j, id2) in ids.iter().enumerate().skip(i + 1) {
+                            if distance(id1, id2) == Ok(k as u32) {
+                                expected.push((i, j));
+                            }
+                        }
+                    }
+                    index.index_pairs_at_distance(k) == expected
+                })
+            },
+        );
+    }
+
+    fn output<'a>(ids: &[&'a str]) -> Output<'a> {
+        Output::new()
+            .field("checksum", checksum(ids, &[2, 3]))
+            .field("pairs_at_distance_1", IdIndex::new(ids).pairs_at_distance(1))
+            .field("common_chars", find_common_chars(ids))
+    }
+
+    #[test]
+    fn it_matches_the_snapshots_for_the_examples() {
+        let checksum_example = ["abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab"];
+        let pair_example = ["abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz"];
+
+        assert_snapshot("./src/y2018/day2/snapshots/example_1.snap", &output(&checksum_example));
+        assert_snapshot("./src/y2018/day2/snapshots/example_2.snap", &output(&pair_example))
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_input() {
+        let strings = map_lines_to_strings("./src/y2018/day2/input");
+        let ids: Vec<&str> = strings.iter().map(|s| s.as_str()).collect();
+
+        assert_snapshot("./src/y2018/day2/snapshots/input.snap", &output(&ids));
+    }
+}
@@ -0,0 +1,271 @@
+use crate::common::parse::*;
+use crate::grid::{Grid, Point, Rect};
+
+use std::error::Error;
+use std::fmt;
+
+type Coord = (u32, u32);
+
+type Claim = (u32, Coord, Coord);
+
+/// The largest fabric, in square inches, that the solvers will allocate.
+pub const MAX_FABRIC_AREA: usize = 1 << 24;
+
+/// The claims reach beyond a fabric of `MAX_FABRIC_AREA`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FabricTooLarge {
+    pub area: usize,
+}
+
+impl fmt::Display for FabricTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the fabric is too large")
+    }
+}
+
+impl Error for FabricTooLarge {}
+
+pub fn solve1(claims: Vec<Claim>) -> u32 {
+    count_overlaps(&claims).unwrap()
+}
+
+pub fn solve2(claims: Vec<Claim>) -> u32 {
+    find_intact_claim(&claims).unwrap().expect("Every claim overlaps another")
+}
+
+/// The number of square inches covered by two or more claims.
+pub fn count_overlaps(claims: &[Claim]) -> Result<u32, FabricTooLarge> {
+    let grid = fill_grid(claims)?;
+
+    Ok(grid.cells().filter(|&&count| count > 1).count() as u32)
+}
+
+/// The id of the first claim that no other claim overlaps. A claim with
+/// no width or height covers no fabric, so it is never intact.
+pub fn find_intact_claim(claims: &[Claim]) -> Result<Option<u32>, FabricTooLarge> {
+    let grid = fill_grid(claims)?;
+
+    Ok(claims
+        .iter()
+        .find(|claim| {
+            let rect = claim_rect(claim);
+            !rect.is_empty() && grid.view(rect).iter().all(|(_, &count)| count == 1)
+        })
+        .map(|&(id, _, _)| id))
+}
+
+/// The area of the smallest fabric, anchored at the origin, that covers
+/// every claim.
+pub fn fabric_area(claims: &[Claim]) -> usize {
+    let bounds = bounds(claims);
+    bounds.max.x.saturating_mul(bounds.max.y)
+}
+
+fn claim_rect(&(_, (x, y), (w, h)): &Claim) -> Rect<usize> {
+    Rect::new(Point::new(x as usize, y as usize), w as usize, h as usize)
+}
+
+/// Counts the number of claims covering each square inch of fabric.
+fn fill_grid(claims: &[Claim]) -> Result<Grid<u32>, FabricTooLarge> {
+    let area = fabric_area(claims);
+    if area > MAX_FABRIC_AREA {
+        return Err(FabricTooLarge { area });
+    }
+
+    let bounds = bounds(claims);
+    let mut grid = Grid::new(bounds.max.x, bounds.max.y, 0);
+
+    for claim in claims {
+        for point in claim_rect(claim).points() {
+            grid[point] += 1;
+        }
+    }
+
+    Ok(grid)
+}
+
+fn bounds(claims: &[Claim]) -> Rect<usize> {
+    claims
+        .iter()
+        .map(claim_rect)
+        .fold(Rect::new(Point::origin(), 0, 0), |bounds, rect| bounds.union(&rect))
+}
+
+pub fn parse_line(line: &str) -> Claim {
+    parse_all(claim, line).unwrap()
+}
+
+/// Parses one claim per non-blank line.
+pub fn parse_input(text: &str) -> Result<Vec<Claim>, ParseError> {
+    parse_lines(claim, text)
+}
+
+/// A claim such as `#123 @ 3,2: 5x4`.
+fn claim(input: Input) -> PResult<Claim> {
+    let (id, input) = delimited(literal("#"), integer(), space0())(input)?;
+    let (_, input) = preceded(space0(), literal("@"))(input)?;
+    let (_, input) = space0()(input)?;
+    let (x, input) = integer()(input)?;
+    let (y, input) = delimited(literal(","), integer(), literal(":"))(input)?;
+    let (w, input) = delimited(space0(), integer(), literal("x"))(input)?;
+    let (h, input) = integer()(input)?;
+
+    Ok(((id, (x, y), (w, h)), input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::*;
+    use crate::check::*;
+    use crate::snapshot::{assert_snapshot, Output};
+    use crate::generate::claims;
+
+    #[test]
+    fn it_returns_num_of_overlapping_claims() {
+        let claims = vec![
+            (1, (1, 3), (4, 4)),
+            (2, (3, 1), (4, 4)),
+            (3, (5, 5), (2, 2)),
+        ];
+
+        assert_eq!(solve1(claims), 4)
+    }
+
+    #[test]
+    fn it_parses_a_line_correctly() {
+        assert_eq!(parse_line("#1 @ 1,3: 4x4"), (1, (1, 3), (4, 4)))
+    }
+
+    #[test]
+    fn it_parses_another_line_correctly() {
+        assert_eq!(parse_line("#123 @ 3,2: 5x4"), (123, (3, 2), (5, 4)))
+    }
+
+    #[test]
+    fn it_handles_input_for_star_1() {
+        let strings = map_lines_to_strings("./src/y2018/day3/input");
+        let claims = strings.iter().map(|s| s.as_str()).map(parse_line).collect();
+
+        assert_eq!(solve1(claims), 119551)
+    }
+
+    #[test]
+    fn it_handles_input_for_star_2() {
+        let strings = map_lines_to_strings("./src/y2018/day3/input");
+        let claims = strings.iter().map(|s| s.as_str()).map(parse_line).collect();
+
+        assert_eq!(solve2(claims), 1124)
+    }
+
+    #[test]
+    fn it_reports_claims_that_all_overlap() {
+        let claims = parse_input("#1 @ 0,0: 2x2\n\n#2 @ 1,1: 2x2\n").unwrap();
+
+        assert_eq!(find_intact_claim(&claims), Ok(None));
+        assert_eq!(fabric_area(&claims), 9);
+        assert_eq!(parse_input("#1 @ 0,0: 2x2\n#2 @ 1,1 2x2").unwrap_err().line, 2)
+    }
+
+    #[test]
+    fn it_never_finds_an_empty_claim_intact() {
+        let claims = parse_input("#1 @ 0,0: 2x2\n#2 @ 1,1: 2x2\n#3 @ 5,5: 0x3").unwrap();
+        assert_eq!(find_intact_claim(&claims), Ok(None));
+
+        let claims = parse_input("#1 @ 0,0: 0x0\n#2 @ 3,3: 1x1").unwrap();
+        assert_eq!(find_intact_claim(&claims), Ok(Some(2)))
+    }
+
+    #[test]
+    fn it_refuses_a_fabric_too_large_to_allocate() {
+        let claims = parse_input("#1 @ 0,0: 2x2\n#2 @ 4096,4096: 1x1").unwrap();
+
+        assert_eq!(count_overlaps(&claims), Err(FabricTooLarge { area: 4097 * 4097 }));
+        assert_eq!(find_intact_claim(&claims), Err(FabricTooLarge { area: 4097 * 4097 }))
+    }
+
+    #[test]
+    fn it_parses_spaces_around_the_at_sign() {
+        let claims = parse_input("#7@1,2: 3x4\n#8  @  1,2: 3x4").unwrap();
+        assert_eq!(claims, vec![(7, (1, 2), (3, 4)), (8, (1, 2), (3, 4))])
+    }
+
+    fn claim_rects(input: &str) -> Vec<(u32, Rect<usize>)> {
+        input
+            .lines()
+            .map(parse_line)
+            .map(|claim| (claim.0, claim_rect(&claim)))
+            .collect()
+    }
+
+    #[test]
+    fn it_counts_overlaps_like_cell_enumeration() {
+        Property::new("./src/y2018/day3/overlap_regressions").check(
+            |rng| {
+                let size = 3 + rng.below(10) as usize;
+                claims(rng, size)
+            },
+            shrink_lines,
+            |case| {
+                let rects = claim_rects(case);
+                let bounds = rects.iter().fold(Rect::new(Point::origin(), 0, 0), |b, (_, r)| b.union(r));
+                let overlapping = bounds
+                    .points()
+                    .filter(|&p| rects.iter().filter(|(_, r)| r.contains(p)).count() > 1)
+                    .count();
+
+                solve1(case.lines().map(parse_line).collect()) as usize == overlapping
+            },
+        );
+    }
+
+    #[test]
+    fn it_finds_the_intact_claim_like_pairwise_intersection() {
+        Property::new("./src/y2018/day3/intact_regressions").check(
+            |rng| {
+                let size = 3 + rng.below(10) as usize;
+                claims(rng, size)
+            },
+            shrink_lines,
+            |case| {
+                let rects = claim_rects(case);
+                let intact = rects
+                    .iter()
+                    .enumerate()
+                    .find(|(i, (_, r))| {
+                        !r.is_empty()
+                            && rects
+                                .iter()
+                                .enumerate()
+                                .all(|(j, (_, o))| j == *i || r.intersection(o).is_none())
+                    })
+                    .map(|(_, &(id, _))| id);
+
+                let claims: Vec<Claim> = case.lines().map(parse_line).collect();
+                find_intact_claim(&claims) == Ok(intact)
+            },
+        );
+    }
+
+    fn output(claims: Vec<Claim>) -> Output<'static> {
+        let bounds = bounds(&claims);
+        let intact_claim = find_intact_claim(&claims).unwrap();
+        Output::new()
+            .field("claims", claims.len())
+            .field("fabric", (bounds.max.x, bounds.max.y))
+            .field("overlapping", solve1(claims))
+            .field("intact_claim", intact_claim)
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_example() {
+        let claims = parse_input("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2").unwrap();
+        assert_snapshot("./src/y2018/day3/snapshots/example.snap", &output(claims))
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_input() {
+        let claims = parse_input(&read_file_to_string("./src/y2018/day3/input")).unwrap();
+        assert_snapshot("./src/y2018/day3/snapshots/input.snap", &output(claims))
+    }
+}
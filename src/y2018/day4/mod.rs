@@ -0,0 +1,769 @@
+use crate::common::parse::*;
+use crate::params;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::ops::RangeInclusive;
+
+type TimeInstant = (u32, u32, u32, u32);
+
+type Timestamp = (u32, TimeInstant);
+
+type Event = (TimeInstant, GuardEvent);
+
+/// For each guard, how many times they are asleep on each minute of the
+/// day, counted from midnight.
+pub type Calendar = HashMap<u32, HashMap<u32, u32>>;
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Debug)]
+pub enum GuardEvent {
+    WakeUp,
+    FallAsleep,
+    BeginShift(u32),
+}
+
+#[derive(Debug)]
+pub enum LogError {
+    Io(io::Error),
+    Parse(ParseError),
+    /// A guard woke up before any guard had begun a shift.
+    NoGuardOnDuty(TimeInstant),
+}
+
+impl fmt::Display for LogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogError::Io(e) => write!(f, "{}", e),
+            LogError::Parse(e) => write!(f, "{}", e),
+            LogError::NoGuardOnDuty((month, day, hour, minute)) => write!(
+                f,
+                "wake-up at {:02}-{:02} {:02}:{:02} before any guard began a shift",
+                month, day, hour, minute
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LogError {}
+
+impl From<io::Error> for LogError {
+    fn from(error: io::Error) -> Self {
+        LogError::Io(error)
+    }
+}
+
+impl From<ParseError> for LogError {
+    fn from(error: ParseError) -> Self {
+        LogError::Parse(error)
+    }
+}
+
+/// The puzzle's parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// The minutes of the day, counted from midnight, in which sleep is
+    /// counted. It is given as `<first>-<last>`, in minutes or as `HH:MM`
+    /// times, such as `0-89` or `00:30-01:29`. The default is the
+    /// midnight hour, `0-59`.
+    pub window: RangeInclusive<u32>,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { window: 0..=59 }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "window" => {
+                let (first, last) = value.split_once('-').ok_or("expected <first>-<last>")?;
+                let (first, last) = (minute_of_day(first)?, minute_of_day(last)?);
+                if first > last {
+                    return Err(format!("{}-{} is not a range of minutes", first, last));
+                }
+                self.window = first..=last;
+            }
+            _ => return Err(params::unknown()),
+        }
+        Ok(())
+    }
+}
+
+/// Parses a minute of the day, as a number of minutes or an `HH:MM` time.
+fn minute_of_day(value: &str) -> Result<u32, String> {
+    let minute = match value.split_once(':') {
+        Some((hour, minute)) => {
+            let (hour, minute): (u32, u32) = (params::parse_value(hour)?, params::parse_value(minute)?);
+            if minute >= 60 {
+                return Err(format!("{:?} is not a time of day", value));
+            }
+            hour.saturating_mul(60).saturating_add(minute)
+        }
+        None => params::parse_value(value)?,
+    };
+    if minute >= MINUTES_PER_DAY {
+        return Err(format!("{:?} is not a minute of the day", value));
+    }
+    Ok(minute)
+}
+
+pub fn solve1(lines: Vec<Event>) -> u64 {
+    solve1_calendar(&populate_calendar(lines).unwrap())
+}
+
+pub fn solve1_calendar(calendar: &Calendar) -> u64 {
+    strategy1(calendar).expect("No guard ever falls asleep")
+}
+
+/// A guard and the minute they are most often asleep. Ties go to the
+/// lowest guard id and the earliest minute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Choice {
+    pub guard: u32,
+    pub minute: u32,
+    /// How many times the guard is asleep on that minute.
+    pub times_asleep: u32,
+}
+
+impl Choice {
+    pub fn answer(&self) -> u64 {
+        self.guard as u64 * self.minute as u64
+    }
+}
+
+/// The guard who sleeps the most in total, times the minute they are most
+/// often asleep, or `None` if no guard ever sleeps.
+pub fn strategy1(calendar: &Calendar) -> Option<u64> {
+    sleepiest_guard(calendar).map(|choice| choice.answer())
+}
+
+pub fn sleepiest_guard(calendar: &Calendar) -> Option<Choice> {
+    choose(calendar, |minutes| minutes.values().map(|&n| n as u64).sum())
+}
+
+/// Picks the guard with the highest `score` among those who sleep. The
+/// calendar is a `HashMap`, so ties are broken explicitly, as `Choice`
+/// describes, to give the same choice on every run.
+fn choose<F: Fn(&HashMap<u32, u32>) -> u64>(calendar: &Calendar, score: F) -> Option<Choice> {
+    let (&guard, minutes) = calendar
+        .iter()
+        .filter(|(_, v)| !v.is_empty())
+        .max_by_key(|&(&guard, v)| (score(v), Reverse(guard)))?;
+
+    let (&minute, &times_asleep) = minutes
+        .iter()
+        .max_by_key(|&(&minute, &no_of_sleeps_of_minute)| (no_of_sleeps_of_minute, Reverse(minute)))?;
+
+    Some(Choice {
+        guard,
+        minute,
+        times_asleep,
+    })
+}
+
+/// Records the sleep of each guard, in timestamp order.
+pub fn populate_calendar(lines: Vec<Event>) -> Result<Calendar, LogError> {
+    populate_calendar_with(lines, &Params::default())
+}
+
+/// Like `populate_calendar`, only counting sleep within `params.window`.
+pub fn populate_calendar_with(mut lines: Vec<Event>, params: &Params) -> Result<Calendar, LogError> {
+    let mut recorder = SleepRecorder::new(params);
+    sort_lines(&mut lines);
+
+    for (time, event) in lines.iter() {
+        recorder.record(*time, event)?;
+    }
+
+    Ok(recorder.calendar)
+}
+
+struct SleepRecorder {
+    calendar: Calendar,
+    current_id: Option<u32>,
+    /// The minute of the day the guard on duty fell asleep, until they
+    /// wake up.
+    asleep_since: Option<u32>,
+    window: RangeInclusive<u32>,
+}
+
+impl SleepRecorder {
+    fn new(params: &Params) -> Self {
+        SleepRecorder {
+            calendar: Calendar::new(),
+            current_id: None,
+            asleep_since: None,
+            window: params.window.clone(),
+        }
+    }
+
+    fn record(&mut self, time: TimeInstant, event: &GuardEvent) -> Result<(), LogError> {
+        let (_, _, hour, min) = time;
+        let minute = hour * 60 + min;
+        match event {
+            GuardEvent::BeginShift(id) => {
+                self.current_id = Some(*id);
+            }
+            GuardEvent::FallAsleep => {
+                self.asleep_since = Some(minute);
+            }
+            GuardEvent::WakeUp => {
+                let id = self.current_id.ok_or(LogError::NoGuardOnDuty(time))?;
+                let vec = self.calendar.entry(id).or_default();
+                // Waking up again without falling asleep counts nothing.
+                let Some(from) = self.asleep_since.take() else {
+                    return Ok(());
+                };
+
+                // A nap that goes past midnight wakes up the next day.
+                let to = if minute < from { minute + MINUTES_PER_DAY } else { minute };
+                for m in (from..to).map(|m| m % MINUTES_PER_DAY) {
+                    if self.window.contains(&m) {
+                        *vec.entry(m).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds the calendar from several unsorted log sources, such as one
+/// reader per log file. Each source is sorted on its own, then the
+/// sources are merged and fed to the calendar one record at a time, in
+/// order of their full timestamp, including the year. Records that
+/// appear more than once are only counted once.
+pub fn ingest_logs<R, I>(sources: I) -> Result<Calendar, LogError>
+where
+    R: BufRead,
+    I: IntoIterator<Item = R>,
+{
+    ingest_logs_with(sources, &Params::default())
+}
+
+/// Like `ingest_logs`, only counting sleep within `params.window`.
+pub fn ingest_logs_with<R, I>(sources: I, params: &Params) -> Result<Calendar, LogError>
+where
+    R: BufRead,
+    I: IntoIterator<Item = R>,
+{
+    let runs = sources
+        .into_iter()
+        .map(sorted_records)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut recorder = SleepRecorder::new(params);
+    for ((_, time), event) in Merge::new(runs) {
+        recorder.record(time, &event)?;
+    }
+
+    Ok(recorder.calendar)
+}
+
+/// The records of one source, sorted by full timestamp.
+fn sorted_records<R: BufRead>(source: R) -> Result<Vec<(Timestamp, GuardEvent)>, LogError> {
+    let mut records = Vec::new();
+    for line in source.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        records.push(parse_all(record, &line)?);
+    }
+
+    records.sort_unstable();
+    Ok(records)
+}
+
+/// A k-way merge of sorted runs, skipping items equal to the one before.
+struct Merge<T> {
+    runs: Vec<std::vec::IntoIter<T>>,
+    /// The next item of each run that has one, smallest first.
+    heads: BinaryHeap<Reverse<(T, usize)>>,
+    last: Option<T>,
+}
+
+impl<T: Ord + Clone> Merge<T> {
+    fn new(runs: Vec<Vec<T>>) -> Self {
+        let mut runs: Vec<_> = runs.into_iter().map(Vec::into_iter).collect();
+        let heads = runs
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, run)| Some(Reverse((run.next()?, i))))
+            .collect();
+
+        Merge { runs, heads, last: None }
+    }
+}
+
+impl<T: Ord + Clone> Iterator for Merge<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let Reverse((item, i)) = self.heads.pop()?;
+            if let Some(next) = self.runs[i].next() {
+                self.heads.push(Reverse((next, i)));
+            }
+            if self.last.as_ref() != Some(&item) {
+                self.last = Some(item.clone());
+                return Some(item);
+            }
+        }
+    }
+}
+
+fn sort_lines(lines: &mut [Event]) {
+    lines.sort_by_key(|(t, _)| *t)
+}
+
+pub fn parse_line(line: &str) -> Event {
+    (parse_time(line), parse_event(line))
+}
+
+/// Parses one event per non-blank line, dropping the year.
+pub fn parse_input(text: &str) -> Result<Vec<Event>, ParseError> {
+    parse_lines(map(record, |((_, time), event)| (time, event)), text)
+}
+
+fn parse_time(line: &str) -> TimeInstant {
+    let (_, instant) = parse_timestamp(line);
+    instant
+}
+
+fn parse_timestamp(line: &str) -> Timestamp {
+    let (timestamp, _) = parse_record(line);
+    timestamp
+}
+
+fn parse_event(line: &str) -> GuardEvent {
+    let (_, event) = parse_record(line);
+    event
+}
+
+fn parse_record(line: &str) -> (Timestamp, GuardEvent) {
+    parse_all(record, line).unwrap()
+}
+
+/// A log line such as `[1518-11-01 00:00] Guard #10 begins shift`.
+fn record(input: Input) -> PResult<(Timestamp, GuardEvent)> {
+    let (timestamp, input) = delimited(literal("["), timestamp, literal("]"))(input)?;
+    let (_, input) = space1()(input)?;
+    let (event, input) = event(input)?;
+
+    Ok(((timestamp, event), input))
+}
+
+fn timestamp(input: Input) -> PResult<Timestamp> {
+    let (year, input) = integer()(input)?;
+    let month = verify(integer(), |m| (1..=12).contains(m), "month");
+    let (month, input) = delimited(literal("-"), month, literal("-"))(input)?;
+    let (day, input) = verify(integer(), |d| (1..=31).contains(d), "day")(input)?;
+    let hour = verify(integer(), |&h| h < 24, "hour");
+    let (hour, input) = delimited(space1(), hour, literal(":"))(input)?;
+    let (minute, input) = verify(integer(), |&m| m < 60, "minute")(input)?;
+
+    Ok(((year, (month, day, hour, minute)), input))
+}
+
+fn event(input: Input) -> PResult<GuardEvent> {
+    let wake_up = map(literal("wakes up"), |_| GuardEvent::WakeUp);
+    let fall_asleep = map(literal("falls asleep"), |_| GuardEvent::FallAsleep);
+    let begin_shift = map(
+        delimited(literal("Guard #"), integer(), literal(" begins shift")),
+        GuardEvent::BeginShift,
+    );
+
+    or(or(wake_up, fall_asleep), begin_shift)(input)
+}
+
+pub fn solve2(lines: Vec<Event>) -> u64 {
+    solve2_calendar(&populate_calendar(lines).unwrap())
+}
+
+pub fn solve2_calendar(calendar: &Calendar) -> u64 {
+    strategy2(calendar).expect("No guard ever falls asleep")
+}
+
+/// The guard who is most often asleep on the same minute, times that
+/// minute, or `None` if no guard ever sleeps.
+pub fn strategy2(calendar: &Calendar) -> Option<u64> {
+    most_regular_sleeper(calendar).map(|choice| choice.answer())
+}
+
+pub fn most_regular_sleeper(calendar: &Calendar) -> Option<Choice> {
+    choose(calendar, |minutes| minutes.values().max().cloned().unwrap_or(0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::*;
+    use crate::snapshot::{assert_snapshot, Output};
+
+    #[test]
+    fn it_parses_wake_up() {
+        let result = parse_event("[1518-11-01 00:25] wakes up");
+
+        assert_eq!(result, GuardEvent::WakeUp)
+    }
+
+    #[test]
+    fn it_parses_falls_asleep() {
+        let result = parse_event("[1518-11-01 00:30] falls asleep");
+
+        assert_eq!(result, GuardEvent::FallAsleep)
+    }
+
+    #[test]
+    fn it_parses_begins_shift() {
+        let result = parse_event("[1518-11-01 00:00] Guard #10 begins shift");
+        assert_eq!(result, GuardEvent::BeginShift(10))
+    }
+
+    #[test]
+    fn it_parses_time() {
+        let result = parse_time("[1518-11-01 00:00] Guard #10 begins shift");
+        assert_eq!(result, (11, 1, 0, 0))
+    }
+
+    #[test]
+    fn it_parses_events() {
+        let result = parse_line("[1518-09-30 12:34] Guard #77 begins shift");
+        assert_eq!(result, ((9, 30, 12, 34), GuardEvent::BeginShift(77)))
+    }
+
+    #[test]
+    fn it_sorts_events() {
+        let mut list = vec![
+            ((11, 4, 0, 36), GuardEvent::FallAsleep),
+            ((11, 1, 0, 30), GuardEvent::FallAsleep),
+            ((11, 1, 0, 55), GuardEvent::WakeUp),
+            ((11, 2, 0, 40), GuardEvent::FallAsleep),
+            ((11, 1, 0, 25), GuardEvent::WakeUp),
+            ((11, 3, 0, 29), GuardEvent::WakeUp),
+            ((11, 2, 0, 50), GuardEvent::WakeUp),
+            ((11, 4, 0,  2), GuardEvent::BeginShift(99)),
+            ((11, 5, 0, 55), GuardEvent::WakeUp),
+            ((11, 5, 0, 3), GuardEvent::BeginShift(99)),
+            ((11, 1, 0, 0), GuardEvent::BeginShift(10)),
+            ((11, 5, 0, 45), GuardEvent::FallAsleep),
+            ((11, 4, 0, 46), GuardEvent::WakeUp),
+            ((11, 1, 0, 5), GuardEvent::FallAsleep),
+            ((11, 1, 23, 58), GuardEvent::BeginShift(99)),
+            ((11, 3, 0, 24), GuardEvent::FallAsleep),
+            ((11, 3, 0, 5), GuardEvent::BeginShift(10)),
+        ];
+
+        sort_lines(&mut list);
+
+        assert_eq!(
+            list,
+            vec![
+                ((11, 1, 0, 0), GuardEvent::BeginShift(10)),
+                ((11, 1, 0, 5), GuardEvent::FallAsleep),
+                ((11, 1, 0, 25), GuardEvent::WakeUp),
+                ((11, 1, 0, 30), GuardEvent::FallAsleep),
+                ((11, 1, 0, 55), GuardEvent::WakeUp),
+                ((11, 1, 23, 58), GuardEvent::BeginShift(99)),
+                ((11, 2, 0, 40), GuardEvent::FallAsleep),
+                ((11, 2, 0, 50), GuardEvent::WakeUp),
+                ((11, 3, 0, 5), GuardEvent::BeginShift(10)),
+                ((11, 3, 0, 24), GuardEvent::FallAsleep),
+                ((11, 3, 0, 29), GuardEvent::WakeUp),
+                ((11, 4, 0,  2), GuardEvent::BeginShift(99)),
+                ((11, 4, 0, 36), GuardEvent::FallAsleep),
+                ((11, 4, 0, 46), GuardEvent::WakeUp),
+                ((11, 5, 0, 3), GuardEvent::BeginShift(99)),
+                ((11, 5, 0, 45), GuardEvent::FallAsleep),
+                ((11, 5, 0, 55), GuardEvent::WakeUp)
+            ]
+        )
+    }
+
+    #[test]
+    fn it_finds_sleeping_minutes() {
+        let list = vec![
+            ((11, 4, 0, 36), GuardEvent::FallAsleep),
+            ((11, 1, 0, 30), GuardEvent::FallAsleep),
+            ((11, 1, 0, 55), GuardEvent::WakeUp),
+            ((11, 2, 0, 40), GuardEvent::FallAsleep),
+            ((11, 1, 0, 25), GuardEvent::WakeUp),
+            ((11, 3, 0, 29), GuardEvent::WakeUp),
+            ((11, 2, 0, 50), GuardEvent::WakeUp),
+            ((11, 4, 0,  2), GuardEvent::BeginShift(99)),
+            ((11, 5, 0, 55), GuardEvent::WakeUp),
+            ((11, 5, 0, 3), GuardEvent::BeginShift(99)),
+            ((11, 1, 0, 0), GuardEvent::BeginShift(10)),
+            ((11, 5, 0, 45), GuardEvent::FallAsleep),
+            ((11, 4, 0, 46), GuardEvent::WakeUp),
+            ((11, 1, 0, 5), GuardEvent::FallAsleep),
+            ((11, 1, 23, 58), GuardEvent::BeginShift(99)),
+            ((11, 3, 0, 24), GuardEvent::FallAsleep),
+            ((11, 3, 0, 5), GuardEvent::BeginShift(10)),
+        ];
+
+        let res = solve1(list);
+
+        assert_eq!(res, 240)
+    }
+
+    #[test]
+    fn it_finds_sleeping_minutes_for_input() {
+        let strings = map_lines_to_strings("./src/y2018/day4/input");
+
+        let list = strings.iter().map(|s| parse_line(s)).collect();
+
+        let res = solve1(list);
+
+        assert_eq!(res, 99911)
+    }
+
+    #[test]
+    fn it_finds_the_most_frequent_asleep_minute() {
+        let list = vec![
+            ((11, 4, 0, 36), GuardEvent::FallAsleep),
+            ((11, 1, 0, 30), GuardEvent::FallAsleep),
+            ((11, 1, 0, 55), GuardEvent::WakeUp),
+            ((11, 2, 0, 40), GuardEvent::FallAsleep),
+            ((11, 1, 0, 25), GuardEvent::WakeUp),
+            ((11, 3, 0, 29), GuardEvent::WakeUp),
+            ((11, 2, 0, 50), GuardEvent::WakeUp),
+            ((11, 4, 0,  2), GuardEvent::BeginShift(99)),
+            ((11, 5, 0, 55), GuardEvent::WakeUp),
+            ((11, 5, 0, 3), GuardEvent::BeginShift(99)),
+            ((11, 1, 0, 0), GuardEvent::BeginShift(10)),
+            ((11, 5, 0, 45), GuardEvent::FallAsleep),
+            ((11, 4, 0, 46), GuardEvent::WakeUp),
+            ((11, 1, 0, 5), GuardEvent::FallAsleep),
+            ((11, 1, 23, 58), GuardEvent::BeginShift(99)),
+            ((11, 3, 0, 24), GuardEvent::FallAsleep),
+            ((11, 3, 0, 5), GuardEvent::BeginShift(10)),
+        ];
+
+        let res = solve2(list);
+
+        assert_eq!(res, 4455)
+    }
+
+    #[test]
+    fn it_finds_sleeping_minute_for_input_star2() {
+        let strings = map_lines_to_strings("./src/y2018/day4/input");
+
+        let list = strings.iter().map(|s| parse_line(s)).collect();
+
+        let res = solve2(list);
+
+        assert_eq!(res, 65854)
+    }
+
+    #[test]
+    fn it_parses_the_full_timestamp() {
+        let result = parse_timestamp("[1518-11-01 00:05] falls asleep");
+        assert_eq!(result, (1518, (11, 1, 0, 5)))
+    }
+
+    #[test]
+    fn it_ingests_unsorted_logs_with_duplicates() {
+        let first = "[1518-11-01 00:25] wakes up
+[1518-11-03 00:29] wakes up
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-05 00:55] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-05 00:45] falls asleep";
+        let second = "[1518-11-02 00:50] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+
+[1518-11-02 00:40] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-03 00:24] falls asleep
+[1518-11-01 00:25] wakes up";
+
+        let calendar = ingest_logs(vec![first.as_bytes(), second.as_bytes()]).unwrap();
+
+        assert_eq!(solve1_calendar(&calendar), 240);
+        assert_eq!(solve2_calendar(&calendar), 4455)
+    }
+
+    #[test]
+    fn it_ingests_the_input_split_across_sources() {
+        let strings = map_lines_to_strings("./src/y2018/day4/input");
+
+        let sources: Vec<String> = strings
+            .chunks(strings.len() / 3 + 1)
+            .rev()
+            .map(|chunk| chunk.join("\n"))
+            .chain(vec![strings[..strings.len() / 2].join("\n")])
+            .collect();
+
+        let calendar = ingest_logs(sources.iter().map(|s| s.as_bytes())).unwrap();
+
+        assert_eq!(solve1_calendar(&calendar), 99911);
+        assert_eq!(solve2_calendar(&calendar), 65854)
+    }
+
+    #[test]
+    fn it_merges_sorted_runs_without_repeats() {
+        let merged: Vec<u32> = Merge::new(vec![vec![1, 4, 4, 9], vec![], vec![2, 4, 10], vec![1]]).collect();
+
+        assert_eq!(merged, vec![1, 2, 4, 9, 10])
+    }
+
+    #[test]
+    fn it_does_not_recount_a_nap_on_a_repeated_wake_up() {
+        let log = "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:10] wakes up
+[1518-11-01 00:20] wakes up";
+        let calendar = populate_calendar(parse_input(log).unwrap()).unwrap();
+
+        assert_eq!(calendar[&10].len(), 5);
+        assert!(calendar[&10].values().all(|&times| times == 1))
+    }
+
+    #[test]
+    fn it_rejects_timestamps_out_of_range() {
+        let error = parse_input("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:60] wakes up").unwrap_err();
+
+        assert_eq!((error.line, error.expected.as_str()), (2, "minute"));
+        assert!(parse_input("[1518-13-01 00:00] wakes up").is_err())
+    }
+
+    #[test]
+    fn it_reports_a_wake_up_before_any_shift() {
+        let log = "[1518-11-01 00:05] falls asleep\n[1518-11-01 00:25] wakes up";
+
+        match ingest_logs(vec![log.as_bytes()]) {
+            Err(LogError::NoGuardOnDuty(time)) => assert_eq!(time, (11, 1, 0, 25)),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_breaks_ties_by_the_lowest_guard_and_the_earliest_minute() {
+        let log = "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:07] wakes up
+[1518-11-02 00:00] Guard #3 begins shift
+[1518-11-02 00:20] falls asleep
+[1518-11-02 00:22] wakes up";
+        let calendar = populate_calendar(parse_input(log).unwrap()).unwrap();
+        let choice = Some(Choice { guard: 3, minute: 20, times_asleep: 1 });
+
+        assert_eq!(sleepiest_guard(&calendar), choice);
+        assert_eq!(most_regular_sleeper(&calendar), choice)
+    }
+
+    #[test]
+    fn it_finds_no_answer_when_nobody_sleeps() {
+        let calendar = populate_calendar(parse_input("[1518-11-01 00:00] Guard #10 begins shift").unwrap()).unwrap();
+        assert_eq!(strategy1(&calendar), None);
+
+        let calendar = populate_calendar(parse_input("[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:00] wakes up").unwrap()).unwrap();
+        assert_eq!(strategy1(&calendar), None);
+        assert_eq!(strategy2(&calendar), None)
+    }
+
+    #[test]
+    fn it_only_counts_sleep_within_the_window() {
+        use crate::params::Params as _;
+
+        let log = read_file_to_string("./src/y2018/day4/input.example");
+        let events = parse_input(&log).unwrap();
+        let mut params = Params::default();
+
+        params.set("window", "0-29").unwrap();
+        let calendar = populate_calendar_with(events.clone(), &params).unwrap();
+        assert!(calendar.values().flat_map(|minutes| minutes.keys()).all(|&m| m < 30));
+        assert_eq!((strategy1(&calendar), strategy2(&calendar)), (Some(240), Some(240)));
+        assert_eq!(ingest_logs_with(vec![log.as_bytes()], &params).unwrap(), calendar);
+
+        params.set("window", "40-49").unwrap();
+        let calendar = populate_calendar_with(events, &params).unwrap();
+        assert_eq!(sleepiest_guard(&calendar), Some(Choice { guard: 99, minute: 45, times_asleep: 3 }));
+
+        assert_eq!(params.set("window", "30-1440"), Err("\"1440\" is not a minute of the day".to_string()));
+        assert_eq!(params.set("window", "30-00:60"), Err("\"00:60\" is not a time of day".to_string()));
+        assert_eq!(params.set("window", "30"), Err("expected <first>-<last>".to_string()));
+        assert_eq!(params.window, 40..=49);
+
+        assert_eq!(params.set("window", "50-40"), Err("50-40 is not a range of minutes".to_string()));
+        params.set("window", "00:30-01:29").unwrap();
+        assert_eq!(params.window, 30..=89)
+    }
+
+    #[test]
+    fn it_counts_sleep_beyond_the_midnight_hour_in_a_longer_window() {
+        use crate::params::Params as _;
+
+        let log = "[1518-11-01 23:50] Guard #10 begins shift
+[1518-11-01 23:55] falls asleep
+[1518-11-02 00:05] wakes up
+[1518-11-02 00:50] falls asleep
+[1518-11-02 01:10] wakes up";
+        let minutes = |window: &str| {
+            let mut params = Params::default();
+            params.set("window", window).unwrap();
+            let calendar = ingest_logs_with(vec![log.as_bytes()], &params).unwrap();
+            let mut minutes: Vec<u32> = calendar[&10].keys().cloned().collect();
+            minutes.sort_unstable();
+            minutes
+        };
+
+        assert_eq!(minutes("0-59"), (0..5).chain(50..60).collect::<Vec<_>>());
+        assert_eq!(minutes("0-119"), (0..5).chain(50..70).collect::<Vec<_>>());
+        assert_eq!(minutes("0-1439"), (0..5).chain(50..70).chain(1435..1440).collect::<Vec<_>>())
+    }
+
+    fn output(log: &str) -> Output<'static> {
+        let calendar = ingest_logs(vec![log.as_bytes()]).unwrap();
+        Output::new()
+            .field("guards", calendar.len())
+            .field("strategy1", sleepiest_guard(&calendar))
+            .field("strategy2", most_regular_sleeper(&calendar))
+            .field("answers", (strategy1(&calendar), strategy2(&calendar)))
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_example() {
+        let log = "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up";
+
+        assert_snapshot("./src/y2018/day4/snapshots/example.snap", &output(log))
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_input() {
+        let log = read_file_to_string("./src/y2018/day4/input");
+        assert_snapshot("./src/y2018/day4/snapshots/input.snap", &output(&log))
+    }
+}
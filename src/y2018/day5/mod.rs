@@ -0,0 +1,378 @@
+use itertools::Itertools;
+
+use std::collections::HashMap;
+
+use crate::params;
+
+/// The puzzle's parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// The unit types, as lowercase letters. The uppercase letter is the
+    /// same type with the opposite polarity.
+    pub alphabet: String,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            alphabet: ('a'..='z').collect(),
+        }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "alphabet" => {
+                if value.is_empty() {
+                    return Err("the alphabet is empty".to_string());
+                }
+                if let Some(c) = value.chars().find(|&c| uppercase(c).is_none()) {
+                    return Err(format!("{:?} is not a lowercase letter", c));
+                }
+                self.alphabet = value.to_string();
+            }
+            _ => return Err(params::unknown()),
+        }
+        Ok(())
+    }
+}
+
+/// The uppercase form of a lowercase letter, if it is a single letter.
+fn uppercase(c: char) -> Option<char> {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) if u != c && c.to_lowercase().eq([c]) => Some(u),
+        _ => None,
+    }
+}
+
+/// The unit type and polarity of each letter in the alphabet, with ASCII
+/// looked up directly since it is most of any polymer.
+struct Units {
+    ascii: [Option<(char, bool)>; 128],
+    other: HashMap<char, (char, bool)>,
+}
+
+impl Units {
+    fn new(params: &Params) -> Self {
+        let mut units = Units {
+            ascii: [None; 128],
+            other: HashMap::new(),
+        };
+        for c in params.alphabet.chars() {
+            if let Some(upper) = uppercase(c) {
+                units.insert(c, (c, false));
+                units.insert(upper, (c, true));
+            }
+        }
+        units
+    }
+
+    fn insert(&mut self, c: char, unit: (char, bool)) {
+        match self.ascii.get_mut(c as usize) {
+            Some(slot) => *slot = Some(unit),
+            None => {
+                self.other.insert(c, unit);
+            }
+        }
+    }
+
+    fn get(&self, c: char) -> Option<(char, bool)> {
+        match self.ascii.get(c as usize) {
+            Some(&unit) => unit,
+            None => self.other.get(&c).copied(),
+        }
+    }
+
+    fn reacts(&self, c1: char, c2: char) -> bool {
+        match (self.get(c1), self.get(c2)) {
+            (Some((t1, p1)), Some((t2, p2))) => t1 == t2 && p1 != p2,
+            _ => false,
+        }
+    }
+
+    /// The type of a unit. Anything else is its own type.
+    fn unit_type(&self, c: char) -> char {
+        self.get(c).map_or(c, |(t, _)| t)
+    }
+}
+
+pub fn solve1(string: &str) -> String {
+    react(string)
+}
+
+/// Whether two adjacent units of the puzzle's alphabet react.
+pub fn react_tuple(c1: char, c2: char) -> bool {
+    Units::new(&Params::default()).reacts(c1, c2)
+}
+
+/// The polymer left once every unit has reacted.
+pub fn react(string: &str) -> String {
+    react_with(string, &Params::default())
+}
+
+/// Like `react`, with the units of `params.alphabet`.
+pub fn react_with(string: &str, params: &Params) -> String {
+    react_units(string, &Units::new(params))
+}
+
+fn react_units(string: &str, units: &Units) -> String {
+    let mut index = 0usize;
+    let mut chars: Vec<_> = string.chars().collect();
+
+    while !chars.is_empty() && index < chars.len() - 1 {
+        let should_remove = units.reacts(chars[index], chars[index + 1]);
+
+        if should_remove {
+            chars.remove(index);
+            chars.remove(index);
+            index = index.saturating_sub(1);
+        } else {
+            index += 1;
+        }
+    }
+
+    chars.iter().collect()
+}
+
+/// The unit types of a polymer, in order of appearance.
+pub fn unique_chars(string: &str) -> String {
+    unique_units(string, &Units::new(&Params::default()))
+}
+
+fn unique_units(string: &str, units: &Units) -> String {
+    string
+        .chars()
+        .map(|c| units.unit_type(c))
+        .unique()
+        .collect()
+}
+
+/// The polymer without any unit of the same type as `ch`.
+pub fn remove_char_from_string(ch: char, string: &str) -> String {
+    remove_units(ch, string, &Units::new(&Params::default()))
+}
+
+fn remove_units(ch: char, string: &str, units: &Units) -> String {
+    let unit_type = units.unit_type(ch);
+    string
+        .chars()
+        .filter(|&c| units.unit_type(c) != unit_type)
+        .collect()
+}
+
+pub fn solve2(string: &str) -> usize {
+    shortest_polymer(string).expect("The polymer has no units")
+}
+
+/// The length of the shortest polymer left after removing every unit of
+/// one type and reacting, or `None` if there are no units to remove.
+pub fn shortest_polymer(string: &str) -> Option<usize> {
+    shortest_polymer_with(string, &Params::default())
+}
+
+/// Like `shortest_polymer`, with the units of `params.alphabet`.
+pub fn shortest_polymer_with(string: &str, params: &Params) -> Option<usize> {
+    lengths_without_each_unit_with(string, params)
+        .into_iter()
+        .map(|(_, length)| length)
+        .min()
+}
+
+/// For every unit type, in order, the length of the reacted polymer once
+/// all units of that type are removed.
+pub fn lengths_without_each_unit(string: &str) -> Vec<(char, usize)> {
+    lengths_without_each_unit_with(string, &Params::default())
+}
+
+fn lengths_without_each_unit_with(string: &str, params: &Params) -> Vec<(char, usize)> {
+    let units = Units::new(params);
+    let reacted_input = react_units(string, &units);
+    let mut polymers: Vec<char> = unique_units(string, &units).chars().collect();
+    polymers.sort_unstable();
+
+    polymers
+        .into_iter()
+        .map(|c| {
+            let polymer = react_units(&remove_units(c, &reacted_input, &units), &units);
+            (c, polymer.chars().count())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::*;
+    use crate::check::*;
+    use crate::snapshot::{assert_snapshot, Output};
+    use crate::generate::polymer;
+    use crate::params::Params as _;
+
+    #[test]
+    fn it_eliminates_two_equal_elements_with_different_polarity() {
+        assert!(react_tuple('a', 'A'))
+    }
+
+    #[test]
+    fn it_keeps_two_equal_elements_with_same_polarity() {
+        assert!(!react_tuple('A', 'A'))
+    }
+
+    #[test]
+    fn it_eliminates_two_equal_elements_in_one_order() {
+        assert!(react_tuple('a', 'A'))
+    }
+
+    #[test]
+    fn it_eliminates_two_equal_elements_in_another_order() {
+        assert!(react_tuple('A', 'a'))
+    }
+
+    #[test]
+    fn it_keeps_two_unequal_elements() {
+        assert!(!react_tuple('a', 'b'))
+    }
+
+    #[test]
+    fn it_works_on_a_string_with_one_reaction() {
+        assert_eq!(react("aA"), "")
+    }
+
+    #[test]
+    fn it_works_on_a_string_with_no_reactions() {
+        assert_eq!(react("abAB"), "abAB")
+    }
+
+    #[test]
+    fn it_works_on_the_first_pass_through_a_sequence_with_no_reactions() {
+        assert_eq!(react("aabAAB"), "aabAAB")
+    }
+    #[test]
+    fn it_works_on_a_larger_string() {
+        assert_eq!(react("dabAcCaCBAcCcaDA"), "dabCBAcaDA")
+    }
+
+    #[test]
+    fn it_counts_the_chars_in_a_larger_string() {
+        assert_eq!(solve1("dabAcCaCBAcCcaDA").len(), 10)
+    }
+
+    #[test]
+    fn it_works_on_the_input() {
+        let string = read_file_to_string("./src/y2018/day5/input");
+        assert_eq!(solve1(&string).len(), 10762)
+    }
+
+    #[test]
+    fn it_uniqs_a_string() {
+        assert_eq!(unique_chars("dabAcCaCBAcCcaDA"), "dabc")
+    }
+
+    #[test]
+    fn it_removes_all_chars_from_string_lowercase() {
+        assert_eq!(
+            remove_char_from_string('a', "dabAcCaCBAcCcaDA"),
+            "dbcCCBcCcD"
+        )
+    }
+
+    #[test]
+    fn it_removes_all_chars_from_string_uppercase() {
+        assert_eq!(
+            remove_char_from_string('A', "dabAcCaCBAcCcaDA"),
+            "dbcCCBcCcD"
+        )
+    }
+
+    #[test]
+    fn it_finds_the_length_of_the_most_efficient_polymer_for_a_string() {
+        assert_eq!(solve2("dabAcCaCBAcCcaDA"), 4)
+    }
+
+    #[test]
+    fn it_finds_the_length_of_the_most_efficient_polymer_for_the_input() {
+        let string = read_file_to_string("./src/y2018/day5/input");
+        assert_eq!(solve2(&string), 6946)
+    }
+
+    #[test]
+    fn it_finds_no_shortest_polymer_without_units() {
+        assert_eq!(shortest_polymer(""), None);
+        assert_eq!(shortest_polymer("dabAcCaCBAcCcaDA"), Some(4))
+    }
+
+    #[test]
+    fn it_reacts_units_of_another_alphabet() {
+        let mut params = Params::default();
+        params.set("alphabet", "aα").unwrap();
+
+        assert_eq!(react_with("aαΑAb", &params), "b");
+        assert_eq!(react_with("aαΑAb", &Params::default()), "aαΑAb");
+        assert_eq!(shortest_polymer_with("αaΑAβ", &params), Some(1))
+    }
+
+    #[test]
+    fn it_removes_units_of_another_alphabet() {
+        let mut params = Params::default();
+        params.set("alphabet", "aα").unwrap();
+        let units = Units::new(&params);
+
+        assert!(units.reacts('α', 'Α'));
+        assert_eq!(unique_units("aαΑAbB", &units), "aαbB");
+        assert_eq!(remove_units('Α', "aαΑAbB", &units), "aAbB")
+    }
+
+    #[test]
+    fn it_rejects_alphabets_without_case() {
+        let mut params = Params::default();
+
+        assert_eq!(params.set("alphabet", ""), Err("the alphabet is empty".to_string()));
+        assert_eq!(params.set("alphabet", "aB"), Err("'B' is not a lowercase letter".to_string()));
+        assert_eq!(params.set("alphabet", "a1"), Err("'1' is not a lowercase letter".to_string()));
+        assert_eq!(params.set("alphabet", "aß"), Err("'ß' is not a lowercase letter".to_string()));
+        assert_eq!(params, Params::default())
+    }
+
+    /// Removes the first reacting pair until there is none left.
+    fn react_by_brute_force(string: &str) -> String {
+        let mut chars: Vec<char> = string.chars().collect();
+
+        while let Some(i) = (1..chars.len()).find(|&i| react_tuple(chars[i - 1], chars[i])) {
+            chars.drain(i - 1..=i);
+        }
+
+        chars.into_iter().collect()
+    }
+
+    #[test]
+    fn it_reacts_like_brute_force_reduction() {
+        Property::new("./src/y2018/day5/regressions").check(
+            |rng| {
+                let size = rng.below(40) as usize;
+                polymer(rng, size, 3)
+            },
+            shrink_chars,
+            |case| react(case) == react_by_brute_force(case),
+        );
+    }
+
+    fn output(polymer: &str) -> Output<'static> {
+        Output::new()
+            .field("reacted_length", solve1(polymer).len())
+            .field("lengths_without_each_unit", lengths_without_each_unit(polymer))
+            .field("shortest", shortest_polymer(polymer))
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_example() {
+        assert_snapshot("./src/y2018/day5/snapshots/example.snap", &output("dabAcCaCBAcCcaDA"))
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_input() {
+        let polymer = read_file_to_string("./src/y2018/day5/input");
+        assert_snapshot("./src/y2018/day5/snapshots/input.snap", &output(&polymer))
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt::Write;
+
+use super::{checked_sum, node_value, Tree, TreeError};
+
+/// Computes the metadata sum (part 1) and value (part 2) of every node,
+/// in pre-order, in a single walk that finishes each node after its
+/// children.
+fn annotate(tree: &Tree<i32>) -> Result<Vec<(i32, i32)>, TreeError> {
+    let mut annotations = vec![(0, 0); tree.node_count()];
+    // A node with its pre-order index once its children have been pushed.
+    let mut stack: Vec<(&Tree<i32>, Option<usize>)> = vec![(tree, None)];
+    let mut finished: Vec<(i32, i32)> = Vec::new();
+    let mut next_index = 0;
+
+    while let Some((node, index)) = stack.pop() {
+        let Some(index) = index else {
+            stack.push((node, Some(next_index)));
+            next_index += 1;
+            stack.extend(node.children.iter().rev().map(|child| (child, None)));
+            continue;
+        };
+
+        let children = finished.split_off(finished.len() - node.children.len());
+        let child_sums = children.iter().map(|&(sum, _)| sum);
+        let child_values: Vec<i32> = children.iter().map(|&(_, value)| value).collect();
+
+        let sum = checked_sum(node.metadata.iter().cloned().chain(child_sums));
+        let value = node_value(&node.metadata, &child_values);
+        let annotation = sum.zip(value).ok_or(TreeError::Overflow)?;
+
+        annotations[index] = annotation;
+        finished.push(annotation);
+    }
+
+    Ok(annotations)
+}
+
+fn join(metadata: &[i32], separator: &str) -> String {
+    metadata
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Renders the tree as a Graphviz digraph. Nodes are numbered in
+/// pre-order and labelled with their metadata, sum and value.
+pub fn to_dot(tree: &Tree<i32>) -> Result<String, TreeError> {
+    let annotations = annotate(tree)?;
+    let mut out = String::from("digraph license {\n    node [shape=box];\n");
+    let mut stack: Vec<(&Tree<i32>, Option<usize>)> = vec![(tree, None)];
+    let mut next_id = 0;
+
+    while let Some((node, parent)) = stack.pop() {
+        let id = next_id;
+        next_id += 1;
+
+        let (sum, value) = annotations[id];
+        writeln!(
+            out,
+            "    n{} [label=\"metadata: {}\\nsum: {}\\nvalue: {}\"];",
+            id,
+            join(&node.metadata, " "),
+            sum,
+            value
+        )
+        .unwrap();
+        if let Some(parent) = parent {
+            writeln!(out, "    n{} -> n{};", parent, id).unwrap();
+        }
+
+        stack.extend(node.children.iter().rev().map(|child| (child, Some(id))));
+    }
+
+    out.push_str("}\n");
+    Ok(out)
+}
+
+/// Renders the tree as nested JSON objects with `metadata`, `sum`,
+/// `value` and `children` fields.
+pub fn to_json(tree: &Tree<i32>) -> Result<String, TreeError> {
+    enum Step<'a> {
+        Open(&'a Tree<i32>),
+        Separator,
+        Close,
+    }
+
+    // Nodes are opened in pre-order, the order of their annotations.
+    let mut annotations = annotate(tree)?.into_iter();
+    let mut out = String::new();
+    let mut steps = vec![Step::Open(tree)];
+
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Open(node) => {
+                let (sum, value) = annotations.next().expect("one annotation per node");
+                write!(
+                    out,
+                    "{{\"metadata\":[{}],\"sum\":{},\"value\":{},\"children\":[",
+                    join(&node.metadata, ","),
+                    sum,
+                    value
+                )
+                .unwrap();
+
+                steps.push(Step::Close);
+                for (i, child) in node.children.iter().enumerate().rev() {
+                    steps.push(Step::Open(child));
+                    if i > 0 {
+                        steps.push(Step::Separator);
+                    }
+                }
+            }
+            Step::Separator => out.push(','),
+            Step::Close => out.push_str("]}"),
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2018::day8::parse_line;
+
+    const EXAMPLE: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+    #[test]
+    fn it_exports_the_example_as_dot() {
+        let dot = to_dot(&parse_line(EXAMPLE)).unwrap();
+
+        assert_eq!(
+            dot,
+            "digraph license {
+    node [shape=box];
+    n0 [label=\"metadata: 1 1 2\\nsum: 138\\nvalue: 66\"];
+    n1 [label=\"metadata: 10 11 12\\nsum: 33\\nvalue: 33\"];
+    n0 -> n1;
+    n2 [label=\"metadata: 2\\nsum: 101\\nvalue: 0\"];
+    n0 -> n2;
+    n3 [label=\"metadata: 99\\nsum: 99\\nvalue: 99\"];
+    n2 -> n3;
+}
+"
+        )
+    }
+
+    #[test]
+    fn it_exports_the_example_as_json() {
+        let json = to_json(&parse_line(EXAMPLE)).unwrap();
+
+        assert_eq!(
+            json,
+            concat!(
+                "{\"metadata\":[1,1,2],\"sum\":138,\"value\":66,\"children\":[",
+                "{\"metadata\":[10,11,12],\"sum\":33,\"value\":33,\"children\":[]},",
+                "{\"metadata\":[2],\"sum\":101,\"value\":0,\"children\":[",
+                "{\"metadata\":[99],\"sum\":99,\"value\":99,\"children\":[]}]}]}"
+            )
+        )
+    }
+
+    #[test]
+    fn it_reports_overflowing_annotations() {
+        let tree = parse_line("1 3 0 1 2147483647 1 1 -2147483648");
+
+        assert_eq!(tree.checked_metadata_sum(), Some(1));
+        assert_eq!(to_json(&tree), Err(TreeError::Overflow));
+        assert_eq!(to_dot(&tree), Err(TreeError::Overflow))
+    }
+}
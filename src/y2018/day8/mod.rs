@@ -0,0 +1,724 @@
+use crate::common::parse::*;
+
+use std::collections::VecDeque;
+use std::fmt;
+
+mod export;
+
+pub use self::export::{to_dot, to_json};
+
+pub struct Tree<T> {
+    metadata: Vec<T>,
+    children: Vec<Tree<T>>,
+}
+
+impl<T> Tree<T> {
+    pub fn new(metadata: Vec<T>, children: Vec<Tree<T>>) -> Self {
+        Tree { metadata, children }
+    }
+
+    pub fn builder() -> TreeBuilder<T> {
+        TreeBuilder {
+            metadata: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn metadata(&self) -> &[T] {
+        &self.metadata
+    }
+
+    pub fn children(&self) -> &[Tree<T>] {
+        &self.children
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Number of levels in the tree, where a single leaf has depth 1.
+    pub fn depth(&self) -> usize {
+        let mut stack = vec![(self, 1)];
+        let mut depth = 0;
+
+        while let Some((node, level)) = stack.pop() {
+            depth = depth.max(level);
+            stack.extend(node.children.iter().map(|child| (child, level + 1)));
+        }
+
+        depth
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.pre_order().count()
+    }
+
+    pub fn pre_order(&self) -> PreOrder<'_, T> {
+        PreOrder { stack: vec![self] }
+    }
+
+    pub fn post_order(&self) -> PostOrder<'_, T> {
+        PostOrder {
+            stack: vec![(self, false)],
+        }
+    }
+
+    pub fn breadth_first(&self) -> BreadthFirst<'_, T> {
+        BreadthFirst {
+            queue: vec![self].into(),
+        }
+    }
+}
+
+/// Writes the tree in the puzzle's license format: a header with the
+/// number of children and metadata entries, then the children, then the
+/// metadata, all separated by single spaces.
+impl<T: fmt::Display> fmt::Display for Tree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        enum Step<'a, T> {
+            Header(&'a Tree<T>),
+            Metadata(&'a Tree<T>),
+        }
+
+        let mut steps = vec![Step::Header(self)];
+        let mut first = true;
+        let mut write_number = |f: &mut fmt::Formatter, n: &dyn fmt::Display| {
+            let separator = if first { "" } else { " " };
+            first = false;
+            write!(f, "{}{}", separator, n)
+        };
+
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Header(node) => {
+                    write_number(f, &node.children.len())?;
+                    write_number(f, &node.metadata.len())?;
+                    steps.push(Step::Metadata(node));
+                    steps.extend(node.children.iter().rev().map(Step::Header));
+                }
+                Step::Metadata(node) => {
+                    for entry in node.metadata.iter() {
+                        write_number(f, entry)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct TreeBuilder<T> {
+    metadata: Vec<T>,
+    children: Vec<Tree<T>>,
+}
+
+impl<T> TreeBuilder<T> {
+    pub fn metadata(mut self, entry: T) -> Self {
+        self.metadata.push(entry);
+        self
+    }
+
+    pub fn child(mut self, child: Tree<T>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn build(self) -> Tree<T> {
+        Tree::new(self.metadata, self.children)
+    }
+}
+
+impl Tree<i32> {
+    /// Sum of all metadata entries in the tree (part 1).
+    pub fn metadata_sum(&self) -> i32 {
+        self.checked_metadata_sum().expect("Metadata sum overflows")
+    }
+
+    /// Value of the root node, following metadata entries as child
+    /// references (part 2).
+    pub fn value(&self) -> i32 {
+        self.checked_value().expect("Node value overflows")
+    }
+
+    /// The metadata sum, or `None` if it does not fit in an `i32`.
+    pub fn checked_metadata_sum(&self) -> Option<i32> {
+        sum_tree(self)
+    }
+
+    /// The value of the root node, or `None` if it or the value of any
+    /// node below it does not fit in an `i32`.
+    pub fn checked_value(&self) -> Option<i32> {
+        sum_tree_by_reference(self)
+    }
+}
+
+// The default drop glue recurses once per level, which overflows the
+// stack for very deep trees, so children are torn down from a worklist.
+impl<T> Drop for Tree<T> {
+    fn drop(&mut self) {
+        let mut pending = std::mem::take(&mut self.children);
+        while let Some(mut node) = pending.pop() {
+            pending.append(&mut node.children);
+        }
+    }
+}
+
+// Like drop, the derived Clone, PartialEq and Debug recurse once per
+// level, so these walk the tree with explicit stacks instead.
+impl<T: Clone> Clone for Tree<T> {
+    fn clone(&self) -> Self {
+        // Children come before their parent in post-order, so each node
+        // takes its clones from the end of `built`.
+        let mut built: Vec<Tree<T>> = Vec::new();
+        for node in self.post_order() {
+            let children = built.split_off(built.len() - node.children.len());
+            built.push(Tree::new(node.metadata.clone(), children));
+        }
+        built.pop().expect("the root is built last")
+    }
+}
+
+impl<T: PartialEq> PartialEq for Tree<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let mut pending = vec![(self, other)];
+        while let Some((left, right)) = pending.pop() {
+            if left.metadata != right.metadata || left.children.len() != right.children.len() {
+                return false;
+            }
+            pending.extend(left.children.iter().zip(right.children.iter()));
+        }
+        true
+    }
+}
+
+impl<T: Eq> Eq for Tree<T> {}
+
+/// Writes the same text as a derived `Debug`, always on one line.
+impl<T: fmt::Debug> fmt::Debug for Tree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        enum Step<'a, T> {
+            Node(&'a Tree<T>),
+            Text(&'static str),
+        }
+
+        let mut steps = vec![Step::Node(self)];
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Node(node) => {
+                    write!(f, "Tree {{ metadata: {:?}, children: [", node.metadata)?;
+                    steps.push(Step::Text("] }"));
+                    for (i, child) in node.children.iter().enumerate().rev() {
+                        steps.push(Step::Node(child));
+                        if i > 0 {
+                            steps.push(Step::Text(", "));
+                        }
+                    }
+                }
+                Step::Text(text) => f.write_str(text)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct PreOrder<'a, T> {
+    stack: Vec<&'a Tree<T>>,
+}
+
+impl<'a, T> Iterator for PreOrder<'a, T> {
+    type Item = &'a Tree<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children.iter().rev());
+        Some(node)
+    }
+}
+
+pub struct PostOrder<'a, T> {
+    stack: Vec<(&'a Tree<T>, bool)>,
+}
+
+impl<'a, T> Iterator for PostOrder<'a, T> {
+    type Item = &'a Tree<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, visited) = self.stack.pop()?;
+            if visited {
+                return Some(node);
+            }
+            self.stack.push((node, true));
+            self.stack
+                .extend(node.children.iter().rev().map(|child| (child, false)));
+        }
+    }
+}
+
+pub struct BreadthFirst<'a, T> {
+    queue: VecDeque<&'a Tree<T>>,
+}
+
+impl<'a, T> Iterator for BreadthFirst<'a, T> {
+    type Item = &'a Tree<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        self.queue.extend(node.children.iter());
+        Some(node)
+    }
+}
+
+pub fn solve1(input: &str) -> i32 {
+    let tree = parse_line(input);
+    tree.metadata_sum()
+}
+
+pub fn solve2(input: &str) -> i32 {
+    let tree = parse_line(input);
+    tree.value()
+}
+
+fn checked_sum<I: IntoIterator<Item = i32>>(values: I) -> Option<i32> {
+    values
+        .into_iter()
+        .try_fold(0i32, |total, value| total.checked_add(value))
+}
+
+/// The child a metadata entry refers to; entries count from 1.
+fn child_index(entry: i32) -> Option<usize> {
+    usize::try_from(entry).ok()?.checked_sub(1)
+}
+
+fn sum_tree(tree: &Tree<i32>) -> Option<i32> {
+    tree.pre_order().try_fold(0i32, |total, node| {
+        total.checked_add(checked_sum(node.metadata.iter().cloned())?)
+    })
+}
+
+fn sum_tree_by_reference(tree: &Tree<i32>) -> Option<i32> {
+    // Post-order visits every child before its parent, so the values of
+    // a node's children are always the topmost entries on the stack.
+    let mut values: Vec<i32> = Vec::new();
+
+    for node in tree.post_order() {
+        let child_values = values.split_off(values.len() - node.children.len());
+
+        values.push(node_value(&node.metadata, &child_values)?);
+    }
+
+    values.pop()
+}
+
+/// The value of a node given the values of its children: the sum of its
+/// metadata for a leaf, otherwise the sum of the children it refers to.
+fn node_value(metadata: &[i32], child_values: &[i32]) -> Option<i32> {
+    if child_values.is_empty() {
+        checked_sum(metadata.iter().cloned())
+    } else {
+        checked_sum(metadata.iter().map(|&i| {
+            child_index(i)
+                .and_then(|i| child_values.get(i))
+                .cloned()
+                .unwrap_or(0)
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeError {
+    Parse(ParseError),
+    /// The numbers ran out before the tree was complete.
+    Truncated,
+    /// A header has a negative number of children or metadata entries.
+    NegativeCount(i32),
+    /// Numbers are left over after the root node.
+    TrailingNumbers(usize),
+    /// A metadata sum or node value does not fit in an `i32`.
+    Overflow,
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TreeError::Parse(e) => write!(f, "{}", e),
+            TreeError::Truncated => write!(f, "input ends inside a node"),
+            TreeError::NegativeCount(n) => write!(f, "negative count {} in a node header", n),
+            TreeError::TrailingNumbers(n) => write!(f, "{} numbers after the root node", n),
+            TreeError::Overflow => write!(f, "a metadata sum or node value overflows"),
+        }
+    }
+}
+
+impl std::error::Error for TreeError {}
+
+impl From<ParseError> for TreeError {
+    fn from(error: ParseError) -> Self {
+        TreeError::Parse(error)
+    }
+}
+
+pub fn parse_line(input: &str) -> Tree<i32> {
+    try_parse_line(input).unwrap()
+}
+
+pub fn try_parse_line(input: &str) -> Result<Tree<i32>, TreeError> {
+    let numbers = separated(integer(), space1());
+    let mut numbers: VecDeque<i32> = parse_all(delimited(space0(), numbers, space0()), input)?.into();
+
+    let tree = parse_node(&mut numbers)?;
+    if numbers.is_empty() {
+        Ok(tree)
+    } else {
+        Err(TreeError::TrailingNumbers(numbers.len()))
+    }
+}
+
+struct PartialNode {
+    remaining_children: i32,
+    num_metadata: i32,
+    children: Vec<Tree<i32>>,
+}
+
+fn parse_header(numbers: &mut VecDeque<i32>) -> Result<PartialNode, TreeError> {
+    let mut count = || match numbers.pop_front() {
+        Some(n) if n < 0 => Err(TreeError::NegativeCount(n)),
+        Some(n) => Ok(n),
+        None => Err(TreeError::Truncated),
+    };
+    let num_children = count()?;
+    let num_metadata = count()?;
+
+    // Every child needs at least a header, which bounds how many there
+    // can really be.
+    let capacity = (num_children as usize).min(numbers.len() / 2);
+
+    Ok(PartialNode {
+        remaining_children: num_children,
+        num_metadata,
+        children: Vec::with_capacity(capacity),
+    })
+}
+
+fn parse_node(numbers: &mut VecDeque<i32>) -> Result<Tree<i32>, TreeError> {
+    let mut stack = vec![parse_header(numbers)?];
+
+    loop {
+        let current = stack.last_mut().unwrap();
+        if current.remaining_children > 0 {
+            current.remaining_children -= 1;
+            let child = parse_header(numbers)?;
+            stack.push(child);
+            continue;
+        }
+
+        let finished = stack.pop().unwrap();
+        if numbers.len() < finished.num_metadata as usize {
+            return Err(TreeError::Truncated);
+        }
+        let metadata: Vec<_> = numbers.drain(..finished.num_metadata as usize).collect();
+        let node = Tree::new(metadata, finished.children);
+
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => return Ok(node),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::*;
+    use crate::check::*;
+    use crate::snapshot::{assert_snapshot, Output};
+    use crate::generate::license_tree;
+    use crate::generate::Rng;
+
+    #[test]
+    fn it_parses_line_into_tree() {
+        let sum = solve1("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2");
+        assert_eq!(sum, 138)
+    }
+
+    #[test]
+    fn it_handles_input_for_star1() {
+        let input = read_file_to_string("./src/y2018/day8/input");
+        let sum = solve1(&input);
+        assert_eq!(sum, 44338)
+    }
+
+    #[test]
+    fn it_sums_by_references() {
+        let sum = solve2("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2");
+        assert_eq!(sum, 66)
+    }
+
+    #[test]
+    fn it_handles_input_for_star2() {
+        let input = read_file_to_string("./src/y2018/day8/input");
+        let sum = solve2(&input);
+        assert_eq!(sum, 37560)
+    }
+
+    const EXAMPLE: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+    #[test]
+    fn it_rejects_malformed_trees() {
+        assert_eq!(try_parse_line("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1"), Err(TreeError::Truncated));
+        assert_eq!(try_parse_line("0 1 5 7"), Err(TreeError::TrailingNumbers(1)));
+        assert_eq!(try_parse_line("1 -1 0 0"), Err(TreeError::NegativeCount(-1)));
+        assert_eq!(try_parse_line("2147483647 0 0 0").err(), Some(TreeError::Truncated));
+        assert!(matches!(try_parse_line("0 1 x"), Err(TreeError::Parse(_))))
+    }
+
+    #[test]
+    fn it_detects_overflowing_sums() {
+        let tree = try_parse_line("1 3 0 1 2147483647 1 1 -2147483648").unwrap();
+
+        assert_eq!(tree.checked_metadata_sum(), Some(1));
+        assert_eq!(tree.checked_value(), None);
+        assert_eq!(try_parse_line("0 1 -5").unwrap().checked_value(), Some(-5))
+    }
+
+    fn first_metadata(nodes: Vec<&Tree<i32>>) -> Vec<i32> {
+        nodes.iter().map(|n| n.metadata()[0]).collect()
+    }
+
+    #[test]
+    fn it_exposes_children_and_metadata() {
+        let tree = parse_line(EXAMPLE);
+
+        assert_eq!(tree.metadata(), &[1, 1, 2]);
+        assert_eq!(tree.children().len(), 2);
+        assert!(tree.children()[0].is_leaf());
+        assert_eq!(tree.children()[1].children()[0].metadata(), &[99])
+    }
+
+    #[test]
+    fn it_counts_nodes_and_depth() {
+        let tree = parse_line(EXAMPLE);
+
+        assert_eq!(tree.node_count(), 4);
+        assert_eq!(tree.depth(), 3);
+        assert_eq!(Tree::new(vec![1], vec![]).depth(), 1)
+    }
+
+    #[test]
+    fn it_iterates_in_pre_post_and_breadth_first_order() {
+        let leaf = |n| Tree::new(vec![n], vec![]);
+        let tree = Tree::new(
+            vec![1],
+            vec![
+                Tree::new(vec![2], vec![leaf(4), leaf(5)]),
+                Tree::new(vec![3], vec![leaf(6)]),
+            ],
+        );
+
+        assert_eq!(first_metadata(tree.pre_order().collect()), vec![1, 2, 4, 5, 3, 6]);
+        assert_eq!(first_metadata(tree.post_order().collect()), vec![4, 5, 2, 6, 3, 1]);
+        assert_eq!(first_metadata(tree.breadth_first().collect()), vec![1, 2, 3, 4, 5, 6])
+    }
+
+    #[test]
+    fn it_computes_the_value_of_a_node() {
+        let tree = parse_line(EXAMPLE);
+
+        assert_eq!(tree.value(), 66);
+        assert_eq!(tree.children()[1].value(), 0);
+        assert_eq!(tree.metadata_sum(), 138)
+    }
+
+    #[test]
+    fn it_handles_a_degenerate_chain_without_recursion() {
+        let depth = 50_000;
+        let mut input = "1 1 ".repeat(depth);
+        input.push_str("0 1 1");
+        input.push_str(&" 1".repeat(depth));
+
+        let tree = parse_line(&input);
+
+        assert_eq!(tree.depth(), depth + 1);
+        assert_eq!(tree.node_count(), depth + 1);
+        assert_eq!(tree.metadata_sum(), depth as i32 + 1);
+        assert_eq!(tree.value(), 1)
+    }
+
+    #[test]
+    fn it_clones_compares_and_debugs_a_degenerate_chain_without_recursion() {
+        let depth = 50_000;
+        let mut input = "1 1 ".repeat(depth);
+        input.push_str("0 1 1");
+        input.push_str(&" 1".repeat(depth));
+
+        let tree = parse_line(&input);
+        let copy = tree.clone();
+        assert_eq!(copy, tree);
+        assert_eq!(format!("{:?}", copy).matches("Tree {").count(), depth + 1);
+
+        input.pop();
+        input.push('2');
+        assert_ne!(parse_line(&input), tree)
+    }
+
+    #[test]
+    fn it_debugs_like_the_derived_format() {
+        let tree = Tree::builder()
+            .child(Tree::builder().metadata(10).build())
+            .child(Tree::builder().build())
+            .metadata(1)
+            .build();
+
+        assert_eq!(
+            format!("{:?}", tree),
+            "Tree { metadata: [1], children: [Tree { metadata: [10], children: [] }, \
+             Tree { metadata: [], children: [] }] }"
+        )
+    }
+
+    #[test]
+    fn it_builds_trees_in_code() {
+        let tree = Tree::builder()
+            .child(Tree::builder().metadata(10).metadata(11).metadata(12).build())
+            .child(Tree::builder().child(Tree::builder().metadata(99).build()).metadata(2).build())
+            .metadata(1)
+            .metadata(1)
+            .metadata(2)
+            .build();
+
+        assert_eq!(tree, parse_line(EXAMPLE))
+    }
+
+    #[test]
+    fn it_serialises_the_example() {
+        assert_eq!(parse_line(EXAMPLE).to_string(), EXAMPLE)
+    }
+
+    #[test]
+    fn it_serialises_a_degenerate_chain_without_recursion() {
+        let depth = 50_000;
+        let mut input = "1 1 ".repeat(depth);
+        input.push_str("0 1 1");
+        input.push_str(&" 1".repeat(depth));
+
+        assert_eq!(parse_line(&input).to_string(), input)
+    }
+
+    #[test]
+    fn it_serialises_the_input() {
+        let input = read_file_to_string("./src/y2018/day8/input");
+        assert_eq!(parse_line(&input).to_string(), input.trim())
+    }
+
+    fn random_tree(rng: &mut Rng, depth: u64) -> Tree<i32> {
+        let num_children = if depth == 0 { 0 } else { rng.below(4) };
+        let num_metadata = rng.below(5);
+
+        let mut builder = Tree::builder();
+        for _ in 0..num_children {
+            builder = builder.child(random_tree(rng, depth - 1));
+        }
+        for _ in 0..num_metadata {
+            builder = builder.metadata(rng.below(100) as i32);
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn it_round_trips_random_trees() {
+        for seed in 0..500 {
+            let mut rng = Rng::new(seed);
+            let tree = random_tree(&mut rng, 5);
+
+            let serialised = tree.to_string();
+            let reparsed = parse_line(&serialised);
+
+            assert_eq!(reparsed, tree, "seed {} produced {}", seed, serialised);
+            assert_eq!(reparsed.to_string(), serialised)
+        }
+    }
+
+    /// The part 2 value straight from the puzzle's recursive definition.
+    fn value_by_definition(tree: &Tree<i32>) -> i32 {
+        if tree.children.is_empty() {
+            tree.metadata.iter().sum()
+        } else {
+            tree.metadata
+                .iter()
+                .filter(|&&i| i >= 1)
+                .filter_map(|&i| tree.children.get(i as usize - 1))
+                .map(value_by_definition)
+                .sum()
+        }
+    }
+
+    fn metadata_sum_by_definition(tree: &Tree<i32>) -> i32 {
+        tree.metadata.iter().sum::<i32>() + tree.children.iter().map(metadata_sum_by_definition).sum::<i32>()
+    }
+
+    /// Smaller trees: any subtree on its own, or the tree with one child,
+    /// one metadata entry, or something within a child removed.
+    fn smaller_trees(tree: &Tree<i32>) -> Vec<Tree<i32>> {
+        let mut candidates: Vec<Tree<i32>> = tree.children.clone();
+
+        for i in 0..tree.children.len() {
+            let mut children = tree.children.clone();
+            children.remove(i);
+            candidates.push(Tree::new(tree.metadata.clone(), children));
+        }
+        for i in 0..tree.metadata.len() {
+            let mut metadata = tree.metadata.clone();
+            metadata.remove(i);
+            candidates.push(Tree::new(metadata, tree.children.clone()));
+        }
+        for (i, child) in tree.children.iter().enumerate() {
+            for smaller in smaller_trees(child) {
+                let mut children = tree.children.clone();
+                children[i] = smaller;
+                candidates.push(Tree::new(tree.metadata.clone(), children));
+            }
+        }
+
+        candidates
+    }
+
+    #[test]
+    fn it_evaluates_like_the_recursive_definition() {
+        Property::new("./src/y2018/day8/regressions").check(
+            |rng| {
+                let size = 1 + rng.below(30) as usize;
+                license_tree(rng, size)
+            },
+            |case| smaller_trees(&parse_line(case)).iter().map(|t| t.to_string()).collect(),
+            |case| {
+                let tree = parse_line(case);
+                tree.value() == value_by_definition(&tree)
+                    && tree.metadata_sum() == metadata_sum_by_definition(&tree)
+            },
+        );
+    }
+
+    fn output(input: &str) -> Output<'static> {
+        let tree = parse_line(input);
+        Output::new()
+            .field("nodes", tree.node_count())
+            .field("depth", tree.depth())
+            .field("leaves", tree.pre_order().filter(|node| node.is_leaf()).count())
+            .field("root_metadata", tree.metadata().to_vec())
+            .field("metadata_sum", tree.metadata_sum())
+            .field("value", tree.value())
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_example() {
+        assert_snapshot("./src/y2018/day8/snapshots/example.snap", &output(EXAMPLE))
+    }
+
+    #[test]
+    fn it_matches_the_snapshot_for_the_input() {
+        let input = read_file_to_string("./src/y2018/day8/input");
+        assert_snapshot("./src/y2018/day8/snapshots/input.snap", &output(&input))
+    }
+}
@@ -0,0 +1,107 @@
+//! The 2018 puzzles, and how the runner solves each day from its input.
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+// mod day6;
+// mod day7;
+pub mod day8;
+
+use crate::common::parse::*;
+use crate::params::Overrides;
+use crate::runner::{Solution, SolveError};
+
+pub const SOLUTIONS: &[Solution] = &[
+    Solution { day: 1, solve: solve_day1 },
+    Solution { day: 2, solve: solve_day2 },
+    Solution { day: 3, solve: solve_day3 },
+    Solution { day: 4, solve: solve_day4 },
+    Solution { day: 5, solve: solve_day5 },
+    Solution { day: 8, solve: solve_day8 },
+];
+
+fn solve_day1(part: u32, input: &str, overrides: &Overrides) -> Result<String, SolveError> {
+    let params: day1::Params = overrides.resolve(1)?;
+    let changes = parse_lines(integer::<i64>(), input)?;
+    if !day1::in_range_with(&changes, &params) {
+        return Err(SolveError::Invalid("the frequencies are too large".to_string()));
+    }
+
+    let answer = if part == 1 {
+        day1::solve1_with(&changes, &params)
+            .ok_or_else(|| SolveError::Invalid("the answer overflows".to_string()))?
+    } else {
+        day1::first_repeat_with(&changes, &params).ok_or(SolveError::NoAnswer)?
+    };
+    Ok(answer.to_string())
+}
+
+fn solve_day2(part: u32, input: &str, overrides: &Overrides) -> Result<String, SolveError> {
+    overrides.resolve::<()>(2)?;
+    let ids: Vec<&str> = input.lines().collect();
+
+    if part == 1 {
+        let answer = day2::checksum(&ids, &[2, 3])
+            .product()
+            .ok_or_else(|| SolveError::Invalid("the answer overflows".to_string()))?;
+        Ok(answer.to_string())
+    } else {
+        day2::find_common_chars(&ids).ok_or(SolveError::NoAnswer)
+    }
+}
+
+fn solve_day3(part: u32, input: &str, overrides: &Overrides) -> Result<String, SolveError> {
+    overrides.resolve::<()>(3)?;
+    let claims = day3::parse_input(input)?;
+    let invalid = |e: day3::FabricTooLarge| SolveError::Invalid(e.to_string());
+
+    if part == 1 {
+        Ok(day3::count_overlaps(&claims).map_err(invalid)?.to_string())
+    } else {
+        let id = day3::find_intact_claim(&claims).map_err(invalid)?.ok_or(SolveError::NoAnswer)?;
+        Ok(id.to_string())
+    }
+}
+
+fn solve_day4(part: u32, input: &str, overrides: &Overrides) -> Result<String, SolveError> {
+    let params: day4::Params = overrides.resolve(4)?;
+    let events = day4::parse_input(input)?;
+    let calendar = day4::populate_calendar_with(events, &params)
+        .map_err(|e| SolveError::Invalid(e.to_string()))?;
+
+    let strategy = if part == 1 { day4::strategy1 } else { day4::strategy2 };
+    let answer = strategy(&calendar).ok_or(SolveError::NoAnswer)?;
+    Ok(answer.to_string())
+}
+
+fn solve_day5(part: u32, input: &str, overrides: &Overrides) -> Result<String, SolveError> {
+    let params: day5::Params = overrides.resolve(5)?;
+    // The polymer is a single line, which may end with a line break.
+    let input = input.trim_end_matches(['\n', '\r']);
+
+    if part == 1 {
+        Ok(day5::react_with(input, &params).chars().count().to_string())
+    } else {
+        let length = day5::shortest_polymer_with(input, &params).ok_or(SolveError::NoAnswer)?;
+        Ok(length.to_string())
+    }
+}
+
+fn solve_day8(part: u32, input: &str, overrides: &Overrides) -> Result<String, SolveError> {
+    overrides.resolve::<()>(8)?;
+    let tree = day8::try_parse_line(input).map_err(|e| match e {
+        day8::TreeError::Parse(e) => SolveError::Parse(e),
+        e => SolveError::Invalid(e.to_string()),
+    })?;
+
+    let answer = if part == 1 {
+        tree.checked_metadata_sum()
+    } else {
+        tree.checked_value()
+    };
+    answer
+        .map(|n| n.to_string())
+        .ok_or_else(|| SolveError::Invalid("the answer overflows".to_string()))
+}